@@ -1,8 +1,27 @@
+use crate::mnemonic::WordCount;
+use crate::solana::{AirdropPolicy, KeypairOutput};
+use std::path::PathBuf;
+
 #[derive(clap::Parser)]
 #[command(name = "solana-exercises-cli")]
 #[command(version = "1.0")]
 #[command(about = "A simple CLI for Solana development", long_about = None)]
 pub struct Cli {
+    /// Cluster to connect to: mainnet, devnet, testnet, localnet or a custom RPC URL.
+    /// Defaults to the Solana CLI config, then devnet
+    #[arg(
+        short = 'u',
+        long,
+        global = true,
+        env = "SOLANA_RPC_URL",
+        value_name = "URL_OR_MONIKER"
+    )]
+    pub cluster: Option<String>,
+
+    /// Keypair file to use instead of the SECRET_KEY variable
+    #[arg(short, long, global = true, value_name = "PATH")]
+    pub keypair: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -10,9 +29,51 @@ pub struct Cli {
 #[derive(clap::Subcommand)]
 pub enum Commands {
     /// Generate a new keypair
-    GenerateKeypair,
-    /// Load keypair from .env file
+    GenerateKeypair {
+        /// Derive the keypair from a new BIP39 seed phrase
+        #[arg(long)]
+        mnemonic: bool,
+        /// Number of words in the seed phrase
+        #[arg(long, value_enum, default_value_t = WordCount::Twelve, requires = "mnemonic")]
+        words: WordCount,
+        /// Optional BIP39 passphrase protecting the seed phrase
+        #[arg(long, requires = "mnemonic")]
+        passphrase: Option<String>,
+        /// Derivation path, e.g. m/44'/501'/0'/0' as used by Phantom and Solflare
+        #[arg(long, requires = "mnemonic")]
+        derivation_path: Option<String>,
+        #[command(flatten)]
+        output: KeypairOutput,
+    },
+    /// Recover a keypair from a seed phrase read from stdin
+    Recover {
+        /// BIP39 passphrase the seed phrase was protected with
+        #[arg(long)]
+        passphrase: Option<String>,
+        /// Derivation path, e.g. m/44'/501'/0'/0' as used by Phantom and Solflare.
+        /// Without it the keypair is derived like `solana-keygen new`
+        #[arg(long)]
+        derivation_path: Option<String>,
+        #[command(flatten)]
+        output: KeypairOutput,
+    },
+    /// Search for a keypair whose address starts and/or ends with the given strings
+    Grind {
+        #[arg(long)]
+        starts_with: Option<String>,
+        #[arg(long)]
+        ends_with: Option<String>,
+        /// Match the strings case-insensitively
+        #[arg(long)]
+        ignore_case: bool,
+        /// Number of worker threads, defaults to the number of CPU cores
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+        num_threads: Option<u16>,
+        #[command(flatten)]
+        output: KeypairOutput,
+    },
+    /// Load keypair from a keypair file or the SECRET_KEY variable
     LoadKeypair,
     /// Check balance and optionally airdrop funds
-    CheckBalance,
+    CheckBalance(AirdropPolicy),
 }
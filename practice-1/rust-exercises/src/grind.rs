@@ -0,0 +1,161 @@
+use anyhow::{bail, Result};
+use solana_sdk::signature::{Keypair, Signer};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::solana::{save_keypair, KeypairOutput};
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+pub struct GrindPattern {
+    starts_with: String,
+    ends_with: String,
+    ignore_case: bool,
+}
+
+impl GrindPattern {
+    pub fn new(
+        starts_with: Option<&str>,
+        ends_with: Option<&str>,
+        ignore_case: bool,
+    ) -> Result<Self> {
+        if starts_with.is_none() && ends_with.is_none() {
+            bail!("At least one of --starts-with or --ends-with is required");
+        }
+
+        let normalize = |value: Option<&str>| -> Result<String> {
+            let value = value.unwrap_or_default();
+            if let Some(c) = value.chars().find(|&c| !is_base58_char(c, ignore_case)) {
+                bail!(
+                    "'{}' contains '{}', which never appears in a base58 address",
+                    value,
+                    c
+                );
+            }
+            Ok(if ignore_case {
+                value.to_lowercase()
+            } else {
+                value.to_string()
+            })
+        };
+
+        Ok(Self {
+            starts_with: normalize(starts_with)?,
+            ends_with: normalize(ends_with)?,
+            ignore_case,
+        })
+    }
+
+    pub fn matches(&self, address: &str) -> bool {
+        let address = if self.ignore_case {
+            Cow::Owned(address.to_lowercase())
+        } else {
+            Cow::Borrowed(address)
+        };
+        address.starts_with(&self.starts_with) && address.ends_with(&self.ends_with)
+    }
+}
+
+fn is_base58_char(c: char, ignore_case: bool) -> bool {
+    BASE58_ALPHABET.contains(c)
+        || (ignore_case
+            && (BASE58_ALPHABET.contains(c.to_ascii_lowercase())
+                || BASE58_ALPHABET.contains(c.to_ascii_uppercase())))
+}
+
+/// Generates keypairs on `num_threads` workers until one matches the pattern
+pub fn grind_keypair(pattern: &GrindPattern, num_threads: usize) -> Keypair {
+    let found = AtomicBool::new(false);
+    let attempts = AtomicU64::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::scope(|scope| {
+        for _ in 0..num_threads {
+            let sender = sender.clone();
+            let (found, attempts) = (&found, &attempts);
+            scope.spawn(move || {
+                while !found.load(Ordering::Relaxed) {
+                    let keypair = Keypair::new();
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    if pattern.matches(&keypair.pubkey().to_string()) {
+                        found.store(true, Ordering::Relaxed);
+                        sender.send(keypair).ok();
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let started = Instant::now();
+        loop {
+            match receiver.recv_timeout(REPORT_INTERVAL) {
+                Ok(keypair) => {
+                    report_progress(attempts.load(Ordering::Relaxed), started.elapsed());
+                    return keypair;
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    report_progress(attempts.load(Ordering::Relaxed), started.elapsed())
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    unreachable!("workers only exit after sending a match")
+                }
+            }
+        }
+    })
+}
+
+fn report_progress(attempts: u64, elapsed: Duration) {
+    let per_second = attempts as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    println!(
+        "Searched {} keypairs in {}s ({:.0} attempts/sec)",
+        attempts,
+        elapsed.as_secs(),
+        per_second
+    );
+}
+
+pub fn grind(pattern: &GrindPattern, num_threads: usize, output: &KeypairOutput) -> Result<()> {
+    println!(
+        "Searching for a matching keypair using {} threads",
+        num_threads
+    );
+
+    let keypair = grind_keypair(pattern, num_threads);
+    save_keypair(&keypair, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_prefix_and_suffix() {
+        let pattern = GrindPattern::new(Some("Ab"), Some("9"), false).unwrap();
+        assert!(pattern.matches("Abc789"));
+        assert!(!pattern.matches("abc789"));
+        assert!(!pattern.matches("Abc788"));
+
+        let pattern = GrindPattern::new(Some("Ab"), None, true).unwrap();
+        assert!(pattern.matches("aBc"));
+    }
+
+    #[test]
+    fn rejects_non_base58_patterns() {
+        assert!(GrindPattern::new(Some("0x"), None, false).is_err());
+        assert!(GrindPattern::new(Some("l"), None, false).is_err());
+        // 'L' is valid base58, so 'l' is allowed when ignoring case
+        assert!(GrindPattern::new(Some("l"), None, true).is_ok());
+        assert!(GrindPattern::new(None, None, false).is_err());
+    }
+
+    #[test]
+    fn grinds_matching_keypair() {
+        let pattern = GrindPattern::new(Some("a"), None, true).unwrap();
+        let keypair = grind_keypair(&pattern, 2);
+        assert!(pattern.matches(&keypair.pubkey().to_string()));
+    }
+}
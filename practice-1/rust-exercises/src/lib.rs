@@ -1,2 +1,4 @@
 pub mod cli;
+pub mod grind;
+pub mod mnemonic;
 pub mod solana;
@@ -2,22 +2,66 @@ use anyhow::Result;
 use clap::Parser;
 use dotenv::dotenv;
 use rust_exercises::cli::{Cli, Commands};
+use rust_exercises::grind::{grind, GrindPattern};
+use rust_exercises::mnemonic::{generate_mnemonic_keypair, recover_keypair};
 use rust_exercises::solana::{
-    check_balance, generate_keypair, load_keypair, print_balance, SolanaCluster,
+    check_balance, generate_keypair, load_keypair, load_rpc_client, print_balance, resolve_rpc_url,
 };
-use solana_client::rpc_client::RpcClient;
 
 fn main() -> Result<()> {
     dotenv().ok();
 
-    let rpc_client = RpcClient::new(String::from(SolanaCluster::Devnet.url()));
-
     let cli = Cli::parse();
+    let rpc_client = load_rpc_client(&resolve_rpc_url(cli.cluster.as_deref())?);
+
     match &cli.command {
-        Commands::GenerateKeypair => generate_keypair()?,
-        Commands::CheckBalance => check_balance(&rpc_client)?,
+        Commands::GenerateKeypair {
+            mnemonic: false,
+            output,
+            ..
+        } => generate_keypair(output)?,
+        Commands::GenerateKeypair {
+            mnemonic: true,
+            words,
+            passphrase,
+            derivation_path,
+            output,
+        } => generate_mnemonic_keypair(
+            *words,
+            passphrase.as_deref().unwrap_or_default(),
+            derivation_path.as_deref(),
+            output,
+        )?,
+        Commands::Recover {
+            passphrase,
+            derivation_path,
+            output,
+        } => recover_keypair(
+            passphrase.as_deref().unwrap_or_default(),
+            derivation_path.as_deref(),
+            output,
+        )?,
+        Commands::CheckBalance(policy) => {
+            let keypair = load_keypair(cli.keypair.as_deref())?;
+            check_balance(&rpc_client, &keypair, policy)?;
+        }
+        Commands::Grind {
+            starts_with,
+            ends_with,
+            ignore_case,
+            num_threads,
+            output,
+        } => {
+            let pattern =
+                GrindPattern::new(starts_with.as_deref(), ends_with.as_deref(), *ignore_case)?;
+            let num_threads = match num_threads {
+                Some(num_threads) => *num_threads as usize,
+                None => std::thread::available_parallelism()?.get(),
+            };
+            grind(&pattern, num_threads, output)?
+        }
         Commands::LoadKeypair => {
-            let keypair = load_keypair()?;
+            let keypair = load_keypair(cli.keypair.as_deref())?;
             println!("Successfully loaded keypair.");
             print_balance(&rpc_client, &keypair)?;
         }
@@ -0,0 +1,161 @@
+use anyhow::{anyhow, Context, Result};
+use bip39::Mnemonic;
+use solana_derivation_path::DerivationPath;
+use solana_keypair::seed_derivable::keypair_from_seed_and_derivation_path;
+use solana_keypair::{keypair_from_seed, Keypair};
+use std::io::BufRead;
+
+use crate::solana::{save_keypair, KeypairOutput};
+
+/// Derivation path used by Phantom and Solflare for the first account
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/501'/0'/0'";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum WordCount {
+    #[value(name = "12")]
+    Twelve,
+    #[value(name = "24")]
+    TwentyFour,
+}
+
+impl WordCount {
+    pub fn count(&self) -> usize {
+        match self {
+            WordCount::Twelve => 12,
+            WordCount::TwentyFour => 24,
+        }
+    }
+}
+
+pub fn generate_mnemonic(words: WordCount) -> Result<Mnemonic> {
+    Mnemonic::generate(words.count()).context("Failed to generate seed phrase")
+}
+
+/// Derives a keypair from a seed phrase the way `solana-keygen` does: without a derivation
+/// path the first 32 bytes of the seed are used, otherwise the SLIP-0010 derived key
+pub fn keypair_from_mnemonic(
+    mnemonic: &Mnemonic,
+    passphrase: &str,
+    derivation_path: Option<&str>,
+) -> Result<Keypair> {
+    let seed = mnemonic.to_seed(passphrase);
+
+    let keypair = match derivation_path {
+        Some(path) => {
+            let path = DerivationPath::from_absolute_path_str(path)
+                .with_context(|| format!("Invalid derivation path: {}", path))?;
+            keypair_from_seed_and_derivation_path(&seed, Some(path))
+        }
+        None => keypair_from_seed(&seed),
+    };
+
+    keypair.map_err(|err| anyhow!("Failed to derive keypair: {}", err))
+}
+
+pub fn generate_mnemonic_keypair(
+    words: WordCount,
+    passphrase: &str,
+    derivation_path: Option<&str>,
+    output: &KeypairOutput,
+) -> Result<()> {
+    let mnemonic = generate_mnemonic(words)?;
+    let keypair = keypair_from_mnemonic(&mnemonic, passphrase, derivation_path)?;
+
+    println!("Save this seed phrase to recover your keypair:");
+    println!("{}", mnemonic);
+    if let Some(path) = derivation_path {
+        println!("Derivation path: {}", path);
+    }
+
+    save_keypair(&keypair, output)
+}
+
+pub fn recover_keypair(
+    passphrase: &str,
+    derivation_path: Option<&str>,
+    output: &KeypairOutput,
+) -> Result<()> {
+    println!("Enter the seed phrase:");
+    let mut phrase = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut phrase)
+        .context("Failed to read seed phrase")?;
+
+    let mnemonic = Mnemonic::parse_normalized(phrase.trim()).context("Invalid seed phrase")?;
+    let keypair = keypair_from_mnemonic(&mnemonic, passphrase, derivation_path)?;
+
+    save_keypair(&keypair, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Signer;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn derive(passphrase: &str, derivation_path: Option<&str>) -> String {
+        let mnemonic = Mnemonic::parse_normalized(PHRASE).unwrap();
+        keypair_from_mnemonic(&mnemonic, passphrase, derivation_path)
+            .unwrap()
+            .pubkey()
+            .to_string()
+    }
+
+    #[test]
+    fn derives_solana_keygen_legacy_keypair() {
+        assert_eq!(
+            derive("", None),
+            "EHqmfkN89RJ7Y33CXM6uCzhVeuywHoJXZZLszBHHZy7o"
+        );
+    }
+
+    #[test]
+    fn derives_phantom_accounts() {
+        assert_eq!(
+            derive("", Some(DEFAULT_DERIVATION_PATH)),
+            "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk"
+        );
+        assert_eq!(
+            derive("", Some("m/44'/501'/1'/0'")),
+            "Hh8QwFUA6MtVu1qAoq12ucvFHNwCcVTV7hpWjeY1Hztb"
+        );
+        assert_eq!(
+            derive("", Some("m/44'/501'")),
+            "D2PPQSYFe83nDzk96FqGumVU8JA7J8vj2Rhjc2oXzEi5"
+        );
+    }
+
+    #[test]
+    fn applies_passphrase() {
+        assert_eq!(
+            derive("TREZOR", None),
+            "6UChi37U4BGomEQR665JZNjTM2PiHcUmBCD3e7TD366v"
+        );
+        assert_eq!(
+            derive("TREZOR", Some(DEFAULT_DERIVATION_PATH)),
+            "7zSmbu6gKkb6HB7UDPtHYjwCWuBHU1D4TpNZFm4sndQe"
+        );
+    }
+
+    #[test]
+    fn generates_requested_word_count() {
+        assert_eq!(
+            generate_mnemonic(WordCount::Twelve).unwrap().word_count(),
+            12
+        );
+        assert_eq!(
+            generate_mnemonic(WordCount::TwentyFour)
+                .unwrap()
+                .word_count(),
+            24
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_derivation_path() {
+        let mnemonic = Mnemonic::parse_normalized(PHRASE).unwrap();
+        assert!(keypair_from_mnemonic(&mnemonic, "", Some("44/501")).is_err());
+    }
+}
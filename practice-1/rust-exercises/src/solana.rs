@@ -1,15 +1,25 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use solana_client::client_error::{reqwest, ClientError, ClientErrorKind};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
-use solana_sdk::native_token::{lamports_to_sol, sol_to_lamports};
-use solana_sdk::signature::{Keypair, Signer};
-use std::fs;
+use solana_sdk::native_token::{sol_str_to_lamports, LAMPORTS_PER_SOL};
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum SolanaCluster {
+    #[value(alias = "mainnet-beta", alias = "m")]
     Mainnet,
+    #[value(alias = "d")]
     Devnet,
+    #[value(alias = "t")]
     Testnet,
+    #[value(alias = "l", alias = "localhost")]
+    Localnet,
 }
 
 impl SolanaCluster {
@@ -18,38 +28,182 @@ impl SolanaCluster {
             SolanaCluster::Mainnet => "https://api.mainnet-beta.solana.com",
             SolanaCluster::Devnet => "https://api.devnet.solana.com",
             SolanaCluster::Testnet => "https://api.testnet.solana.com",
+            SolanaCluster::Localnet => "http://localhost:8899",
         }
     }
 }
 
-pub fn load_rpc_client(cluster: SolanaCluster) -> RpcClient {
-    RpcClient::new(cluster.url().to_string())
+impl FromStr for SolanaCluster {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        <Self as clap::ValueEnum>::from_str(s, true)
+            .map_err(|_| anyhow::anyhow!("Unknown cluster: {}", s))
+    }
+}
+
+/// Resolves the RPC URL from a cluster moniker or custom URL, falling back to the
+/// Solana CLI config file and finally to devnet
+pub fn resolve_rpc_url(cluster: Option<&str>) -> Result<String> {
+    if let Some(cluster) = cluster {
+        if let Ok(cluster) = cluster.parse::<SolanaCluster>() {
+            return Ok(cluster.url().to_string());
+        }
+        if cluster.starts_with("http://") || cluster.starts_with("https://") {
+            return Ok(cluster.to_string());
+        }
+        anyhow::bail!(
+            "Invalid cluster '{}', expected mainnet, devnet, testnet, localnet or an http(s) URL",
+            cluster
+        );
+    }
+
+    let config_url = load_cli_config()
+        .map(|config| config.json_rpc_url)
+        .filter(|url| !url.is_empty());
+
+    Ok(config_url.unwrap_or_else(|| SolanaCluster::Devnet.url().to_string()))
+}
+
+/// Subset of the Solana CLI `~/.config/solana/cli/config.yml`
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct CliConfig {
+    pub json_rpc_url: String,
+}
+
+pub fn load_cli_config() -> Option<CliConfig> {
+    let home = std::env::var_os("HOME")?;
+    let path = Path::new(&home).join(".config/solana/cli/config.yml");
+    let content = fs::read_to_string(path).ok()?;
+    serde_yaml::from_str(&content).ok()
+}
+
+pub fn load_rpc_client(url: &str) -> RpcClient {
+    RpcClient::new(url.to_string())
 }
 
-pub fn generate_keypair() -> Result<()> {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum KeypairFormat {
+    /// Solana CLI compatible JSON array of the 64 secret key bytes
+    Json,
+    /// `SECRET_KEY`/`PUBLIC_KEY` entries for a .env file
+    Dotenv,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct KeypairOutput {
+    /// File to write the keypair to [default: keypair.json, or .env with --format dotenv]
+    #[arg(short, long)]
+    pub outfile: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t = KeypairFormat::Json)]
+    pub format: KeypairFormat,
+    /// Overwrite the output file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    /// Print the secret key to stdout
+    #[arg(long)]
+    pub show_secret: bool,
+}
+
+impl KeypairOutput {
+    /// `--outfile`, defaulting to a file name matching the format
+    pub fn path(&self) -> PathBuf {
+        self.outfile.clone().unwrap_or_else(|| match self.format {
+            KeypairFormat::Json => PathBuf::from("keypair.json"),
+            KeypairFormat::Dotenv => PathBuf::from(".env"),
+        })
+    }
+}
+
+pub fn generate_keypair(output: &KeypairOutput) -> Result<()> {
     let wallet = Keypair::new();
+    save_keypair(&wallet, output)
+}
+
+pub fn save_keypair(wallet: &Keypair, output: &KeypairOutput) -> Result<()> {
     let public_key = wallet.pubkey();
     let secret_key = wallet.to_bytes();
 
     println!("Public key: {}", public_key);
-    println!("Private key: {:?}", secret_key);
+    if output.show_secret {
+        println!("Private key: {:?}", secret_key);
+    }
 
-    let env_content = format!(
-        "SECRET_KEY=\"{:?}\"\nPUBLIC_KEY=\"{}\"",
-        secret_key, public_key
-    );
+    let content = match output.format {
+        KeypairFormat::Json => serde_json::to_string(&secret_key.to_vec())?,
+        KeypairFormat::Dotenv => format!(
+            "SECRET_KEY=\"{:?}\"\nPUBLIC_KEY=\"{}\"\n",
+            secret_key, public_key
+        ),
+    };
 
-    fs::write(".env", env_content).context("Failed to write .env file")?;
+    let path = output.path();
+    write_secret_file(&path, content.as_bytes(), output.force)?;
 
-    println!(".env file has been written with the secret and public keys.");
+    println!("Keypair has been written to {}", path.display());
     Ok(())
 }
 
-pub fn load_keypair() -> Result<Keypair> {
+/// Writes a file readable only by the owner, refusing to replace an existing one unless `force`
+fn write_secret_file(path: &Path, content: &[u8], force: bool) -> Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if force {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path).map_err(|err| match err.kind() {
+        std::io::ErrorKind::AlreadyExists => anyhow::anyhow!(
+            "{} already exists, use --force to overwrite it",
+            path.display()
+        ),
+        _ => anyhow::Error::new(err).context(format!("Failed to create {}", path.display())),
+    })?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        // `mode` only applies to newly created files
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+
+    file.write_all(content)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Loads the keypair from a keypair file if given, otherwise from the `SECRET_KEY` variable
+pub fn load_keypair(keypair_path: Option<&Path>) -> Result<Keypair> {
+    if let Some(path) = keypair_path {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read keypair file {}", path.display()))?;
+        return parse_secret_key(&content)
+            .with_context(|| format!("Failed to load keypair from {}", path.display()));
+    }
+
     let secret_key_str =
         std::env::var("SECRET_KEY").context("SECRET_KEY environment variable not found")?;
-    let secret_key: Vec<u8> = serde_json::from_str(&secret_key_str)
-        .context("Failed to parse SECRET_KEY from environment variable")?;
+    parse_secret_key(&secret_key_str)
+        .context("Failed to parse SECRET_KEY from environment variable")
+}
+
+/// Accepts a JSON byte array (Solana CLI) or a base58 string (Phantom export)
+pub fn parse_secret_key(secret_key_str: &str) -> Result<Keypair> {
+    let secret_key_str = secret_key_str.trim();
+    let secret_key: Vec<u8> = if secret_key_str.starts_with('[') {
+        serde_json::from_str(secret_key_str).context("Invalid JSON secret key")?
+    } else {
+        bs58::decode(secret_key_str)
+            .into_vec()
+            .context("Invalid base58 secret key")?
+    };
 
     let keypair =
         Keypair::from_bytes(&secret_key).context("Failed to load keypair from secret key")?;
@@ -63,7 +217,7 @@ pub fn print_balance(rpc_client: &RpcClient, wallet: &Keypair) -> Result<u64> {
     let balance = rpc_client
         .get_balance(&public_key)
         .context("Failed to get balance")?;
-    let balance_in_sol = lamports_to_sol(balance);
+    let balance_in_sol = balance as f64 / LAMPORTS_PER_SOL as f64;
 
     println!("Public key: {}", public_key);
     println!("Balance: {} lamports ({} SOL)", balance, balance_in_sol);
@@ -71,34 +225,383 @@ pub fn print_balance(rpc_client: &RpcClient, wallet: &Keypair) -> Result<u64> {
     Ok(balance)
 }
 
-pub fn check_balance(rpc_client: &RpcClient) -> Result<()> {
-    let wallet = load_keypair()?;
+/// Genesis hash of mainnet-beta, used to detect mainnet behind custom RPC URLs
+const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+/// Largest airdrop the devnet/testnet faucets grant per request
+pub const MAX_AIRDROP_LAMPORTS: u64 = 2 * LAMPORTS_PER_SOL;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Commitment {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl Commitment {
+    pub fn config(&self) -> CommitmentConfig {
+        match self {
+            Commitment::Processed => CommitmentConfig::processed(),
+            Commitment::Confirmed => CommitmentConfig::confirmed(),
+            Commitment::Finalized => CommitmentConfig::finalized(),
+        }
+    }
+}
+
+/// Parses a SOL amount such as `1.5` into lamports
+pub fn parse_sol(value: &str) -> Result<u64> {
+    sol_str_to_lamports(value).ok_or_else(|| anyhow::anyhow!("Invalid SOL amount: {}", value))
+}
+
+#[derive(Debug, clap::Args)]
+pub struct AirdropPolicy {
+    /// Airdrop when the balance is below this many SOL
+    #[arg(long, value_name = "SOL", default_value = "1", value_parser = parse_sol)]
+    pub min_balance: u64,
+    /// Amount of SOL to airdrop, split into requests of at most 2 SOL
+    #[arg(long, value_name = "SOL", default_value = "1", value_parser = parse_sol)]
+    pub airdrop_amount: u64,
+    /// Commitment level to confirm the airdrop at
+    #[arg(long, value_enum, default_value_t = Commitment::Processed)]
+    pub commitment: Commitment,
+    /// How many times to retry an airdrop request rejected by the faucet rate limit
+    #[arg(long, default_value_t = 5)]
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled after each attempt
+    #[arg(long, value_name = "MS", default_value_t = 1000)]
+    pub retry_delay_ms: u64,
+}
+
+pub fn check_balance(
+    rpc_client: &RpcClient,
+    wallet: &Keypair,
+    policy: &AirdropPolicy,
+) -> Result<Vec<Signature>> {
     println!("Connected to {}", rpc_client.url());
 
-    let balance = print_balance(&rpc_client, &wallet)?;
-    if balance < sol_to_lamports(1.0) {
-        request_airdrop(&rpc_client, &wallet)?;
+    let balance = print_balance(rpc_client, wallet)?;
+    if balance >= policy.min_balance || policy.airdrop_amount == 0 {
+        return Ok(Vec::new());
     }
 
-    Ok(())
+    request_airdrop(rpc_client, wallet, policy)
 }
 
-pub fn request_airdrop(rpc_client: &RpcClient, wallet: &Keypair) -> Result<()> {
-    let airdrop_signature = rpc_client
-        .request_airdrop(&wallet.pubkey(), sol_to_lamports(1.0))
-        .context("Failed to request airdrop")?;
-    let recent_blockhash = rpc_client
-        .get_latest_blockhash()
-        .context("Failed to get recent blockhash")?;
-
-    rpc_client
-        .confirm_transaction_with_spinner(
-            &airdrop_signature,
-            &recent_blockhash,
-            CommitmentConfig::processed(),
+pub fn is_mainnet(rpc_client: &RpcClient) -> Result<bool> {
+    let genesis_hash = rpc_client
+        .get_genesis_hash()
+        .context("Failed to get genesis hash")?;
+    Ok(genesis_hash.to_string() == MAINNET_GENESIS_HASH)
+}
+
+pub fn request_airdrop(
+    rpc_client: &RpcClient,
+    wallet: &Keypair,
+    policy: &AirdropPolicy,
+) -> Result<Vec<Signature>> {
+    if is_mainnet(rpc_client)? {
+        bail!("Refusing to request an airdrop on mainnet");
+    }
+
+    let mut signatures = Vec::new();
+    let mut remaining = policy.airdrop_amount;
+    while remaining > 0 {
+        let lamports = remaining.min(MAX_AIRDROP_LAMPORTS);
+
+        let airdrop_signature = retry_with_backoff(
+            policy.max_retries,
+            Duration::from_millis(policy.retry_delay_ms),
+            || Ok(rpc_client.request_airdrop(&wallet.pubkey(), lamports)?),
         )
-        .context("Failed to confirm airdrop transaction")?;
+        .context("Failed to request airdrop")?;
+        let recent_blockhash = rpc_client
+            .get_latest_blockhash()
+            .context("Failed to get recent blockhash")?;
+
+        rpc_client
+            .confirm_transaction_with_spinner(
+                &airdrop_signature,
+                &recent_blockhash,
+                policy.commitment.config(),
+            )
+            .context("Failed to confirm airdrop transaction")?;
+
+        println!(
+            "✅- Airdropped {} lamports, signature: {}",
+            lamports, airdrop_signature
+        );
+        signatures.push(airdrop_signature);
+        remaining -= lamports;
+    }
 
     println!("✅- Airdrop completed");
-    Ok(())
+    Ok(signatures)
+}
+
+/// Runs `operation`, retrying with exponential backoff while the faucet reports rate limiting
+pub fn retry_with_backoff<T>(
+    max_retries: u32,
+    initial_delay: Duration,
+    mut operation: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    let mut delay = initial_delay;
+    let mut attempt = 0;
+    loop {
+        match operation() {
+            Err(err) if attempt < max_retries && is_rate_limited(&err) => {
+                attempt += 1;
+                println!(
+                    "Faucet rate limit hit, retrying in {:?} ({}/{})",
+                    delay, attempt, max_retries
+                );
+                std::thread::sleep(delay);
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+pub fn is_rate_limited(err: &anyhow::Error) -> bool {
+    let Some(err) = err.downcast_ref::<ClientError>() else {
+        return false;
+    };
+    if let ClientErrorKind::Reqwest(err) = err.kind() {
+        if err.status() == Some(reqwest::StatusCode::TOO_MANY_REQUESTS) {
+            return true;
+        }
+    }
+
+    let message = err.to_string().to_lowercase();
+    ["429", "too many requests", "rate limit", "airdrop limit"]
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use solana_client::rpc_request::{RpcError, RpcRequest};
+    use solana_client::rpc_response::{Response, RpcResponseContext};
+    use std::collections::HashMap;
+
+    const DEVNET_GENESIS_HASH: &str = "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG";
+
+    fn policy(min_balance: u64, airdrop_amount: u64) -> AirdropPolicy {
+        AirdropPolicy {
+            min_balance,
+            airdrop_amount,
+            commitment: Commitment::Confirmed,
+            max_retries: 3,
+            retry_delay_ms: 0,
+        }
+    }
+
+    fn mock_client(balance: u64, genesis_hash: &str) -> RpcClient {
+        let mut mocks = HashMap::new();
+        mocks.insert(
+            RpcRequest::GetBalance,
+            json!(Response {
+                context: RpcResponseContext {
+                    slot: 1,
+                    api_version: None
+                },
+                value: balance,
+            }),
+        );
+        mocks.insert(RpcRequest::GetGenesisHash, json!(genesis_hash));
+        RpcClient::new_mock_with_mocks("succeeds", mocks)
+    }
+
+    fn rate_limit_error() -> anyhow::Error {
+        ClientError::from(RpcError::RpcResponseError {
+            code: 429,
+            message: "Too many requests for a specific RPC call".to_string(),
+            data: solana_client::rpc_request::RpcResponseErrorData::Empty,
+        })
+        .into()
+    }
+
+    #[test]
+    fn skips_airdrop_above_min_balance() {
+        let client = mock_client(LAMPORTS_PER_SOL, DEVNET_GENESIS_HASH);
+        let signatures = check_balance(
+            &client,
+            &Keypair::new(),
+            &policy(LAMPORTS_PER_SOL, LAMPORTS_PER_SOL),
+        )
+        .unwrap();
+        assert!(signatures.is_empty());
+    }
+
+    #[test]
+    fn splits_airdrop_into_faucet_sized_requests() {
+        let client = mock_client(0, DEVNET_GENESIS_HASH);
+        let signatures = check_balance(
+            &client,
+            &Keypair::new(),
+            &policy(LAMPORTS_PER_SOL, 5 * LAMPORTS_PER_SOL),
+        )
+        .unwrap();
+        assert_eq!(signatures.len(), 3);
+    }
+
+    #[test]
+    fn refuses_airdrop_on_mainnet() {
+        let client = mock_client(0, MAINNET_GENESIS_HASH);
+        let err = check_balance(
+            &client,
+            &Keypair::new(),
+            &policy(LAMPORTS_PER_SOL, LAMPORTS_PER_SOL),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("mainnet"));
+    }
+
+    #[test]
+    fn retries_rate_limited_requests() {
+        let mut calls = 0;
+        let result = retry_with_backoff(3, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 {
+                Err(rate_limit_error())
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn gives_up_after_max_retries() {
+        let mut calls = 0;
+        let result: Result<()> = retry_with_backoff(2, Duration::ZERO, || {
+            calls += 1;
+            Err(rate_limit_error())
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn does_not_retry_other_errors() {
+        let mut calls = 0;
+        let result: Result<()> = retry_with_backoff(3, Duration::ZERO, || {
+            calls += 1;
+            Err(ClientError::from(RpcError::ForUser("Invalid pubkey".to_string())).into())
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn parses_cluster_monikers() {
+        assert_eq!(
+            "mainnet-beta".parse::<SolanaCluster>().unwrap(),
+            SolanaCluster::Mainnet
+        );
+        assert_eq!("D".parse::<SolanaCluster>().unwrap(), SolanaCluster::Devnet);
+        assert_eq!(
+            "localhost".parse::<SolanaCluster>().unwrap(),
+            SolanaCluster::Localnet
+        );
+        assert!("devnett".parse::<SolanaCluster>().is_err());
+    }
+
+    #[test]
+    fn resolves_rpc_urls() {
+        assert_eq!(
+            resolve_rpc_url(Some("testnet")).unwrap(),
+            "https://api.testnet.solana.com"
+        );
+        assert_eq!(
+            resolve_rpc_url(Some("https://rpc.example.com")).unwrap(),
+            "https://rpc.example.com"
+        );
+        let err = resolve_rpc_url(Some("ftp://rpc.example.com")).unwrap_err();
+        assert!(err.to_string().contains("Invalid cluster"));
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{}", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn parses_json_and_base58_secret_keys() {
+        let keypair = Keypair::new();
+        let json = serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap();
+
+        assert_eq!(
+            parse_secret_key(&format!(" {}\n", json)).unwrap().pubkey(),
+            keypair.pubkey()
+        );
+        assert_eq!(
+            parse_secret_key(&keypair.to_base58_string())
+                .unwrap()
+                .pubkey(),
+            keypair.pubkey()
+        );
+        assert!(parse_secret_key("[1, 2, 3]").is_err());
+        assert!(parse_secret_key("not-base58!").is_err());
+    }
+
+    #[test]
+    fn refuses_to_overwrite_without_force() {
+        let path = temp_path("overwrite.json");
+
+        write_secret_file(&path, b"first", false).unwrap();
+        let err = write_secret_file(&path, b"second", false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        assert_eq!(fs::read(&path).unwrap(), b"first");
+
+        write_secret_file(&path, b"second", true).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"second");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn writes_secret_files_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let path = temp_path("mode.json");
+        fs::write(&path, b"old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_secret_file(&path, b"secret", true).unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn defaults_outfile_to_format() {
+        let output = |format, outfile: Option<&str>| KeypairOutput {
+            outfile: outfile.map(PathBuf::from),
+            format,
+            force: false,
+            show_secret: false,
+        };
+
+        assert_eq!(
+            output(KeypairFormat::Json, None).path(),
+            PathBuf::from("keypair.json")
+        );
+        assert_eq!(
+            output(KeypairFormat::Dotenv, None).path(),
+            PathBuf::from(".env")
+        );
+        assert_eq!(
+            output(KeypairFormat::Dotenv, Some("wallet.env")).path(),
+            PathBuf::from("wallet.env")
+        );
+    }
+
+    #[test]
+    fn parses_sol_amounts() {
+        assert_eq!(parse_sol("1.5").unwrap(), 1_500_000_000);
+        assert!(parse_sol("abc").is_err());
+    }
 }
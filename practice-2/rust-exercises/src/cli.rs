@@ -1,11 +1,19 @@
+use crate::commands::output::{Explorer, OutputFormat};
+use crate::commands::transaction::Commitment;
 use crate::commands::{
-    create_token_account, create_token_metadata, create_token_mint, mint_tokens, send_sol,
+    broadcast, burn, close_token_account, create_token_account, create_token_metadata,
+    create_token_mint, distribute, favorites, freeze_account, inspect, metadata_json, mint_tokens,
+    multisig, nonce, send_sol, set_authority, show_token_metadata, transfer_tokens, tx,
+    update_token_metadata,
 };
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[clap(author, version, about)]
 pub struct Cli {
+    #[clap(flatten)]
+    pub global: GlobalArgs,
     #[clap(subcommand)]
     pub command: Commands,
 }
@@ -16,5 +24,93 @@ pub enum Commands {
     CreateTokenMint(create_token_mint::CreateTokenMintArgs),
     CreateTokenAccount(create_token_account::CreateTokenAccountArgs),
     MintTokens(mint_tokens::MintTokensArgs),
+    /// Transfer tokens to a wallet's associated token account
+    TransferTokens(transfer_tokens::TransferTokensArgs),
+    /// Send SOL or tokens to every recipient of a CSV file
+    Distribute(distribute::DistributeArgs),
+    /// Burn tokens from a token account
+    Burn(burn::BurnArgs),
+    /// Freeze a token account with the mint's freeze authority
+    FreezeAccount(freeze_account::FreezeAccountArgs),
+    /// Thaw a frozen token account
+    ThawAccount(freeze_account::FreezeAccountArgs),
+    /// Close an empty token account and reclaim its rent
+    CloseTokenAccount(close_token_account::CloseTokenAccountArgs),
+    /// Change or revoke a mint or token account authority
+    SetAuthority(set_authority::SetAuthorityArgs),
     CreateTokenMetadata(create_token_metadata::CreateTokenMetadataArgs),
+    /// Update the name, symbol or URI of a token's metadata, or make it immutable
+    UpdateTokenMetadata(update_token_metadata::UpdateTokenMetadataArgs),
+    /// Show the metadata of a token
+    ShowTokenMetadata(show_token_metadata::ShowTokenMetadataArgs),
+    /// Write an off-chain metadata JSON file, ready to be uploaded
+    MetadataJson(metadata_json::MetadataJsonArgs),
+    Favorites(favorites::FavoritesArgs),
+    /// Manage durable nonce accounts
+    Nonce(nonce::NonceArgs),
+    /// Send a transaction signed earlier with --sign-only
+    Broadcast(broadcast::BroadcastArgs),
+    /// Build, co-sign, inspect and send multi-signer transactions
+    Tx(tx::TxArgs),
+    /// Manage SPL token multisig accounts
+    Multisig(multisig::MultisigArgs),
+    /// Fetch any account and decode it by its detected type
+    Inspect(inspect::InspectArgs),
+}
+
+#[derive(Args, Default)]
+pub struct GlobalArgs {
+    /// Keypair file, falls back to SECRET_KEY (or .env) and then ~/.config/solana/id.json
+    #[clap(long, global = true, env = "SOLANA_KEYPAIR", value_name = "PATH")]
+    pub keypair: Option<PathBuf>,
+    /// Output format of command results
+    #[clap(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+    /// Simulate the transaction and report logs, compute units, fee and balance changes
+    #[clap(long, global = true, conflicts_with = "sign_only")]
+    pub dry_run: bool,
+    /// Sign the transaction without sending it and print it serialized
+    #[clap(long, global = true)]
+    pub sign_only: bool,
+    /// Durable nonce account to use instead of a recent blockhash
+    #[clap(long, global = true, value_name = "PUBKEY")]
+    pub nonce: Option<String>,
+    /// Keypair file of the nonce authority, defaults to the fee payer
+    #[clap(long, global = true, value_name = "PATH")]
+    pub nonce_authority: Option<PathBuf>,
+    /// Priority fee in micro-lamports per compute unit
+    #[clap(
+        long,
+        global = true,
+        value_name = "MICRO_LAMPORTS",
+        conflicts_with = "auto_priority_fee"
+    )]
+    pub priority_fee: Option<u64>,
+    /// Maximum compute units the transaction may consume
+    #[clap(long, global = true, value_name = "UNITS")]
+    pub compute_unit_limit: Option<u32>,
+    /// Priority fee at this percentile of the fees recently paid to write the same accounts
+    /// [default: 75]
+    #[clap(
+        long,
+        global = true,
+        value_name = "PERCENTILE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "75",
+        value_parser = clap::value_parser!(u8).range(0..=100)
+    )]
+    pub auto_priority_fee: Option<u8>,
+    /// Times to retry sending, re-signing with a new blockhash once the previous one expired
+    #[clap(long, global = true, value_name = "COUNT", default_value_t = 3)]
+    pub max_retries: u32,
+    /// Commitment level to fetch the blockhash, run preflight checks and confirm at
+    #[clap(long, global = true, value_enum, default_value_t = Commitment::Finalized)]
+    pub commitment: Commitment,
+    /// Send without simulating the transaction first
+    #[clap(long, global = true)]
+    pub skip_preflight: bool,
+    /// Block explorer to link signatures and addresses to
+    #[clap(long, global = true, value_enum, default_value_t = Explorer::Solana)]
+    pub explorer: Explorer,
 }
@@ -0,0 +1,17 @@
+use solana_program::hash::hash;
+
+/// Anchor programs of this repository, see practice-3
+pub const FAVORITES_PROGRAM: &str = "3r8Gwzy7K1RgJv8LgoyWhvU79tw4FvoEqp8urmLwhLD4";
+pub const ESCROW_PROGRAM: &str = "Gsf9ZkQa4TZovkUrGw3ENPzGBD55vFdh4Z2fUKuxGj8i";
+pub const ESCROW_APPROVE_PROGRAM: &str = "7dL76EVtNbko1FzuSsfSMjZhPHZ88nQBspYouCAkUwsA";
+
+/// Length of the prefix Anchor tags its accounts and instructions with
+pub const ANCHOR_DISCRIMINATOR_SIZE: usize = 8;
+
+/// Anchor discriminator of `name` in `namespace`, e.g. `account` or `global`
+pub fn discriminator(namespace: &str, name: &str) -> [u8; ANCHOR_DISCRIMINATOR_SIZE] {
+    let mut discriminator = [0u8; ANCHOR_DISCRIMINATOR_SIZE];
+    discriminator
+        .copy_from_slice(&hash(format!("{}:{}", namespace, name).as_bytes()).to_bytes()[..8]);
+    discriminator
+}
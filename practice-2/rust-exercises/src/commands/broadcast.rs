@@ -0,0 +1,115 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::transaction::{decode_transaction, dispatch_transaction, TransactionOutcome};
+use anyhow::{bail, Result};
+use clap::Args;
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+
+#[derive(Args)]
+pub struct BroadcastArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    /// Base64 or base58 transaction printed by --sign-only
+    #[clap(long)]
+    pub transaction: String,
+}
+
+#[derive(Serialize)]
+pub struct BroadcastOutput {
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for BroadcastOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Transaction broadcast");
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        self.transaction.explorer_links()
+    }
+}
+
+pub fn execute(args: BroadcastArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    let output = run(&client, global, args)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    args: BroadcastArgs,
+) -> Result<BroadcastOutput> {
+    let transaction = decode_transaction(&args.transaction)?;
+    if !transaction.is_signed() {
+        bail!("Transaction is missing signatures");
+    }
+
+    Ok(BroadcastOutput {
+        transaction: dispatch_transaction(client, global, &transaction)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::MockRpc;
+    use base64::prelude::{Engine, BASE64_STANDARD};
+    use solana_sdk::hash::Hash;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::transaction::Transaction;
+
+    fn args(transaction: &Transaction) -> BroadcastArgs {
+        BroadcastArgs {
+            cluster: String::new(),
+            transaction: BASE64_STANDARD.encode(bincode::serialize(transaction).unwrap()),
+        }
+    }
+
+    #[test]
+    fn sends_signed_transaction() {
+        let rpc = MockRpc::new();
+        let payer = Keypair::new();
+        let transaction = Transaction::new_signed_with_payer(
+            &[solana_sdk::system_instruction::transfer(
+                &payer.pubkey(),
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        );
+
+        run(&rpc.client, &GlobalArgs::default(), args(&transaction)).unwrap();
+
+        assert_eq!(rpc.sent_transaction(), transaction);
+    }
+
+    #[test]
+    fn rejects_unsigned_transaction() {
+        let rpc = MockRpc::new();
+        let payer = Pubkey::new_unique();
+        let transaction = Transaction::new_unsigned(solana_sdk::message::Message::new(
+            &[solana_sdk::system_instruction::transfer(
+                &payer,
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&payer),
+        ));
+
+        assert!(run(&rpc.client, &GlobalArgs::default(), args(&transaction)).is_err());
+        assert!(rpc.sent_transactions().is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::{
+    fetch_mint, format_token_amount, parse_token_amount, resolve_token_account,
+};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[derive(Args)]
+pub struct BurnArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    #[clap(long)]
+    pub mint: String,
+    /// Amount of tokens, e.g. 12.34, scaled by the mint's decimals
+    #[clap(long)]
+    pub amount: String,
+    /// Token account to burn from, defaults to the loaded keypair's associated token account
+    #[clap(long)]
+    pub token_account: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct BurnOutput {
+    pub mint: String,
+    pub token_account: String,
+    /// Amount in base units
+    pub amount: u64,
+    pub ui_amount: String,
+    pub decimals: u8,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for BurnOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Tokens burned");
+        println!("🔥 - Amount: {}", self.ui_amount);
+        println!("🔑 - Token account: {}", self.token_account);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([
+            ExplorerLink::Address(&self.mint),
+            ExplorerLink::Address(&self.token_account),
+        ]);
+        links
+    }
+}
+
+pub fn execute(args: BurnArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    let sender = crate::commands::load_keypair(global)?;
+    let output = run(&client, global, &sender, args)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    args: BurnArgs,
+) -> Result<BurnOutput> {
+    let sender_pubkey = sender.pubkey();
+    let mint: Pubkey = args.mint.parse()?;
+
+    let mint_info = fetch_mint(client, &mint)?;
+    let amount = parse_token_amount(&args.amount, mint_info.decimals)?;
+    let token_account = resolve_token_account(
+        args.token_account.as_deref(),
+        &sender_pubkey,
+        &mint,
+        &mint_info.program_id,
+    )?;
+
+    let burn_instruction = spl_token_2022::instruction::burn_checked(
+        &mint_info.program_id,
+        &token_account,
+        &mint,
+        &sender_pubkey,
+        &[],
+        amount,
+        mint_info.decimals,
+    )?;
+
+    let transaction = process_transaction(
+        client,
+        global,
+        &[burn_instruction],
+        &sender_pubkey,
+        &[sender],
+    )?;
+
+    Ok(BurnOutput {
+        mint: mint.to_string(),
+        token_account: token_account.to_string(),
+        amount,
+        ui_amount: format_token_amount(amount, mint_info.decimals),
+        decimals: mint_info.decimals,
+        transaction,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        account_response, assert_instructions, mint_account, MockRpc,
+    };
+    use solana_client::rpc_request::RpcRequest;
+
+    #[test]
+    fn burns_from_associated_account() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let account = mint_account(&spl_token::ID, &sender.pubkey(), 2);
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&mint, &account),
+            )]
+            .into(),
+        );
+        let args = BurnArgs {
+            cluster: String::new(),
+            mint: mint.to_string(),
+            amount: "0.25".to_string(),
+            token_account: None,
+        };
+
+        run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[spl_token::instruction::burn_checked(
+                &spl_token::ID,
+                &spl_associated_token_account::get_associated_token_address(
+                    &sender.pubkey(),
+                    &mint,
+                ),
+                &mint,
+                &sender.pubkey(),
+                &[],
+                25,
+                2,
+            )
+            .unwrap()],
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::{fetch_mint, resolve_token_account};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[derive(Args)]
+pub struct CloseTokenAccountArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    #[clap(long)]
+    pub mint: String,
+    /// Token account to close, defaults to the loaded keypair's associated token account
+    #[clap(long)]
+    pub token_account: Option<String>,
+    /// Account receiving the reclaimed rent, defaults to the loaded keypair
+    #[clap(long)]
+    pub destination: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CloseTokenAccountOutput {
+    pub token_account: String,
+    pub destination: String,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for CloseTokenAccountOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Token account closed");
+        println!("🔑 - Closed account: {}", self.token_account);
+        println!("💰 - Rent sent to: {}", self.destination);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([
+            ExplorerLink::Address(&self.token_account),
+            ExplorerLink::Address(&self.destination),
+        ]);
+        links
+    }
+}
+
+pub fn execute(args: CloseTokenAccountArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    let sender = crate::commands::load_keypair(global)?;
+    let output = run(&client, global, &sender, args)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    args: CloseTokenAccountArgs,
+) -> Result<CloseTokenAccountOutput> {
+    let sender_pubkey = sender.pubkey();
+    let mint: Pubkey = args.mint.parse()?;
+    let destination = match &args.destination {
+        Some(destination) => destination.parse()?,
+        None => sender_pubkey,
+    };
+
+    let mint_info = fetch_mint(client, &mint)?;
+    let token_account = resolve_token_account(
+        args.token_account.as_deref(),
+        &sender_pubkey,
+        &mint,
+        &mint_info.program_id,
+    )?;
+
+    let close_instruction = spl_token_2022::instruction::close_account(
+        &mint_info.program_id,
+        &token_account,
+        &destination,
+        &sender_pubkey,
+        &[],
+    )?;
+
+    let transaction = process_transaction(
+        client,
+        global,
+        &[close_instruction],
+        &sender_pubkey,
+        &[sender],
+    )?;
+
+    Ok(CloseTokenAccountOutput {
+        token_account: token_account.to_string(),
+        destination: destination.to_string(),
+        transaction,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        account_response, assert_instructions, mint_account, MockRpc,
+    };
+    use solana_client::rpc_request::RpcRequest;
+
+    #[test]
+    fn closes_account_into_destination() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let token_account = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let account = mint_account(&spl_token::ID, &sender.pubkey(), 2);
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&mint, &account),
+            )]
+            .into(),
+        );
+        let args = CloseTokenAccountArgs {
+            cluster: String::new(),
+            mint: mint.to_string(),
+            token_account: Some(token_account.to_string()),
+            destination: Some(destination.to_string()),
+        };
+
+        run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[spl_token::instruction::close_account(
+                &spl_token::ID,
+                &token_account,
+                &destination,
+                &sender.pubkey(),
+                &[],
+            )
+            .unwrap()],
+        );
+    }
+}
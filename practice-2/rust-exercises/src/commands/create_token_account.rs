@@ -1,8 +1,17 @@
-use anyhow::Result;
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::{fetch_mint, MintInfo};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
+use anyhow::{bail, Result};
 use clap::Args;
+use serde::Serialize;
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::signature::Signer;
-use spl_associated_token_account::instruction::create_associated_token_account;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token_2022::extension::{ExtensionType, StateWithExtensions};
+use std::path::PathBuf;
 
 #[derive(Args)]
 pub struct CreateTokenAccountArgs {
@@ -10,52 +19,417 @@ pub struct CreateTokenAccountArgs {
     pub cluster: String,
     #[clap(long)]
     pub token_mint: String,
+    /// Wallet owning the token account (defaults to the loaded keypair)
+    #[clap(long)]
+    pub owner: Option<String>,
+    /// Keypair file of a new token account to create instead of the associated token account
+    #[clap(long, value_name = "PATH")]
+    pub keypair_account: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+pub struct CreateTokenAccountOutput {
+    pub token_account: String,
+    pub mint: String,
+    pub owner: String,
+    pub created: bool,
+    /// `None` when the account already existed
+    #[serde(flatten)]
+    pub transaction: Option<TransactionOutcome>,
+}
+
+impl CommandOutput for CreateTokenAccountOutput {
+    fn print_text(&self) {
+        match &self.transaction {
+            Some(transaction) => transaction.print_text("Token account created"),
+            None => println!("❌ - Token account already exists"),
+        }
+        println!("🔑 - Token account address: {}", self.token_account);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self
+            .transaction
+            .as_ref()
+            .map_or_else(Vec::new, TransactionOutcome::explorer_links);
+        links.extend([
+            ExplorerLink::Address(&self.token_account),
+            ExplorerLink::Address(&self.mint),
+            ExplorerLink::Address(&self.owner),
+        ]);
+        links
+    }
 }
 
-pub fn execute(args: CreateTokenAccountArgs) -> Result<()> {
+pub fn execute(args: CreateTokenAccountArgs, global: &GlobalArgs) -> Result<()> {
     let client = RpcClient::new(args.cluster.clone());
-    println!("Connected to {}", args.cluster);
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
 
-    let sender = crate::commands::load_keypair()?;
+    let sender = crate::commands::load_keypair(global)?;
+    let account_keypair = args
+        .keypair_account
+        .as_deref()
+        .map(crate::commands::read_keypair_file)
+        .transpose()?;
+    let output = run(&client, global, &sender, account_keypair.as_ref(), args)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+/// Creates the associated token account of the owner, or `account_keypair` when given,
+/// under the token program of the mint unless a matching token account already exists
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    account_keypair: Option<&Keypair>,
+    args: CreateTokenAccountArgs,
+) -> Result<CreateTokenAccountOutput> {
     let token_mint = args.token_mint.parse()?;
 
     let sender_pubkey = sender.pubkey();
+    let owner = match &args.owner {
+        Some(owner) => owner.parse()?,
+        None => sender_pubkey,
+    };
 
-    let associated_token =
-        spl_associated_token_account::get_associated_token_address(&sender_pubkey, &token_mint);
+    let mint_info = fetch_mint(client, &token_mint)?;
+    let program_id = mint_info.program_id;
+    let token_account = match account_keypair {
+        Some(account_keypair) => account_keypair.pubkey(),
+        None => spl_associated_token_account::get_associated_token_address_with_program_id(
+            &owner,
+            &token_mint,
+            &program_id,
+        ),
+    };
 
-    match client.get_account(&associated_token) {
-        Ok(_account) => {
-            println!("❌ - Token account already exists");
-            println!("🔑 - Token account address: {}", associated_token);
-        }
-        Err(_) => {
-            println!("🚀 - Creating token account");
-            let create_token_account_instruction = create_associated_token_account(
+    let mut output = CreateTokenAccountOutput {
+        token_account: token_account.to_string(),
+        mint: token_mint.to_string(),
+        owner: owner.to_string(),
+        created: false,
+        transaction: None,
+    };
+
+    if token_account_exists(
+        client,
+        global,
+        &token_account,
+        &token_mint,
+        &owner,
+        &program_id,
+    )? {
+        return Ok(output);
+    }
+
+    let (instructions, signers): (Vec<Instruction>, Vec<&dyn Signer>) = match account_keypair {
+        Some(account_keypair) => (
+            keypair_account_instructions(
+                client,
                 &sender_pubkey,
+                &token_account,
+                &token_mint,
+                &owner,
+                &mint_info,
+            )?,
+            vec![sender, account_keypair],
+        ),
+        None => (
+            vec![create_associated_token_account_idempotent(
                 &sender_pubkey,
+                &owner,
                 &token_mint,
-                &spl_token::ID,
-            );
+                &program_id,
+            )],
+            vec![sender],
+        ),
+    };
+
+    let transaction = process_transaction(client, global, &instructions, &sender_pubkey, &signers)?;
+
+    output.created = transaction.signature().is_some();
+    output.transaction = Some(transaction);
+    Ok(output)
+}
+
+/// Whether `address` already holds a token account of `mint` owned by `owner` at the
+/// `--commitment` level; an account holding anything else is an error rather than a reason
+/// to skip creation
+fn token_account_exists(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    address: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<bool> {
+    let Some(account) = client
+        .get_account_with_commitment(address, global.commitment.config())?
+        .value
+    else {
+        return Ok(false);
+    };
+
+    if account.owner != *program_id {
+        bail!("{} exists but is not owned by {}", address, program_id);
+    }
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+        .map_err(|_| anyhow::anyhow!("{} exists but is not a token account", address))?;
+    if state.base.mint != *mint {
+        bail!(
+            "Token account {} belongs to mint {}, not {}",
+            address,
+            state.base.mint,
+            mint
+        );
+    }
+    if state.base.owner != *owner {
+        bail!(
+            "Token account {} is owned by {}, not {}",
+            address,
+            state.base.owner,
+            owner
+        );
+    }
+    Ok(true)
+}
 
-            let recent_blockhash = client.get_latest_blockhash()?;
+/// Allocates a token account at a fresh keypair, sized for the extensions the mint requires
+fn keypair_account_instructions(
+    client: &RpcClient,
+    payer: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    mint_info: &MintInfo,
+) -> Result<Vec<Instruction>> {
+    let program_id = &mint_info.program_id;
+    let account_len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+        &ExtensionType::get_required_init_account_extensions(&mint_info.extensions),
+    )?;
+    let rent = client.get_minimum_balance_for_rent_exemption(account_len)?;
 
-            let trx = solana_sdk::transaction::Transaction::new_signed_with_payer(
-                &[create_token_account_instruction],
-                Some(&sender_pubkey),
-                &[&sender],
-                recent_blockhash,
-            );
+    Ok(vec![
+        solana_sdk::system_instruction::create_account(
+            payer,
+            token_account,
+            rent,
+            account_len as u64,
+            program_id,
+        ),
+        spl_token_2022::instruction::initialize_account3(program_id, token_account, mint, owner)?,
+    ])
+}
 
-            let signature = client.send_and_confirm_transaction_with_spinner(&trx)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        account_response, assert_instructions, mint_account, token_account, MockRpc,
+    };
+    use crate::commands::transaction::Commitment;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_sdk::program_pack::Pack;
 
-            println!(
-                "✅ - Token account created, transaction signature: {}",
-                signature
-            );
-            println!("🔑 - Token account address: {}", associated_token);
+    fn args(mint: &Pubkey) -> CreateTokenAccountArgs {
+        CreateTokenAccountArgs {
+            cluster: String::new(),
+            token_mint: mint.to_string(),
+            owner: None,
+            keypair_account: None,
         }
     }
 
-    Ok(())
+    /// Mocks the mint, token accounts are then reported missing
+    fn rpc_with_mint(mint: &Pubkey, program_id: &Pubkey) -> MockRpc {
+        let account = mint_account(program_id, &Pubkey::new_unique(), 2);
+        MockRpc::with_mocks([(RpcRequest::GetAccountInfo, account_response(mint, &account))].into())
+    }
+
+    #[test]
+    fn creates_missing_associated_account() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &spl_token::ID);
+
+        let output = run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            None,
+            args(&mint),
+        )
+        .unwrap();
+
+        assert!(output.created);
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[create_associated_token_account_idempotent(
+                &sender.pubkey(),
+                &sender.pubkey(),
+                &mint,
+                &spl_token::ID,
+            )],
+        );
+    }
+
+    #[test]
+    fn creates_token_2022_account_for_other_owner() {
+        let sender = Keypair::new();
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &spl_token_2022::ID);
+        let args = CreateTokenAccountArgs {
+            owner: Some(owner.to_string()),
+            ..args(&mint)
+        };
+
+        let output = run(&rpc.client, &GlobalArgs::default(), &sender, None, args).unwrap();
+
+        assert_eq!(
+            output.token_account,
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                &owner,
+                &mint,
+                &spl_token_2022::ID,
+            )
+            .to_string()
+        );
+        assert_eq!(output.owner, owner.to_string());
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[create_associated_token_account_idempotent(
+                &sender.pubkey(),
+                &owner,
+                &mint,
+                &spl_token_2022::ID,
+            )],
+        );
+    }
+
+    #[test]
+    fn creates_keypair_account() {
+        let sender = Keypair::new();
+        let account_keypair = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &spl_token::ID);
+
+        run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            Some(&account_keypair),
+            args(&mint),
+        )
+        .unwrap();
+
+        // The mock RPC reports 20 lamports as the rent exempt minimum
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[
+                solana_sdk::system_instruction::create_account(
+                    &sender.pubkey(),
+                    &account_keypair.pubkey(),
+                    20,
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token_2022::instruction::initialize_account3(
+                    &spl_token::ID,
+                    &account_keypair.pubkey(),
+                    &mint,
+                    &sender.pubkey(),
+                )
+                .unwrap(),
+            ],
+        );
+    }
+
+    #[test]
+    fn finds_existing_account() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let address = spl_associated_token_account::get_associated_token_address(&owner, &mint);
+        let account = token_account(&spl_token::ID, &mint, &owner);
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&address, &account),
+            )]
+            .into(),
+        );
+
+        let global = GlobalArgs {
+            commitment: Commitment::Processed,
+            ..GlobalArgs::default()
+        };
+
+        assert!(token_account_exists(
+            &rpc.client,
+            &global,
+            &address,
+            &mint,
+            &owner,
+            &spl_token::ID
+        )
+        .unwrap());
+        // An account created a moment ago is found at the commitment the command runs with
+        let params = rpc.params(RpcRequest::GetAccountInfo);
+        assert_eq!(params[0][1]["commitment"], "processed");
+    }
+
+    #[test]
+    fn rejects_existing_account_of_other_mint() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let address = Pubkey::new_unique();
+        let account = token_account(&spl_token::ID, &Pubkey::new_unique(), &owner);
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&address, &account),
+            )]
+            .into(),
+        );
+
+        let error = token_account_exists(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &address,
+            &mint,
+            &owner,
+            &spl_token::ID,
+        )
+        .unwrap_err();
+
+        assert!(error.to_string().contains("belongs to mint"));
+    }
+
+    #[test]
+    fn rejects_non_token_mint() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &solana_sdk::system_program::ID);
+
+        let error = run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            None,
+            args(&mint),
+        )
+        .err()
+        .unwrap();
+
+        assert!(error.to_string().contains("not owned by a token program"));
+        assert!(rpc.sent_transactions().is_empty());
+    }
 }
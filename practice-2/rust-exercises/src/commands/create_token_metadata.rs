@@ -1,10 +1,19 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token_metadata::{fetch_native_metadata_mint, validate_metadata};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
 use anyhow::Result;
 use clap::Args;
 use mpl_token_metadata::accounts::Metadata;
 use mpl_token_metadata::instructions::CreateV1Builder;
 use mpl_token_metadata::types::TokenStandard;
+use serde::Serialize;
 use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
 use solana_sdk::signer::Signer;
+use spl_token_metadata_interface::state::TokenMetadata;
 
 #[derive(Args)]
 pub struct CreateTokenMetadataArgs {
@@ -12,52 +21,272 @@ pub struct CreateTokenMetadataArgs {
     pub cluster: String,
     #[clap(long)]
     pub token_mint: String,
-    #[clap(long, default_value = "Solana UA Bootcamp 2024-08-06 RUST")]
+    #[clap(long, default_value = "Solana UA Bootcamp RUST")]
     pub name: String,
     #[clap(long, default_value = "SOLTEST")]
     pub symbol: String,
     #[clap(long, default_value = "https://arweave.net/1234")]
     pub uri: String,
+    /// Store the metadata in the Token-2022 mint itself instead of a Metaplex account, the mint
+    /// needs a metadata pointer to itself
+    #[clap(long)]
+    pub native_metadata: bool,
+}
+
+#[derive(Serialize)]
+pub struct CreateTokenMetadataOutput {
+    pub mint: String,
+    pub metadata: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for CreateTokenMetadataOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Token metadata created");
+        println!("🔑 - Token mint address: {}", self.mint);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([
+            ExplorerLink::Address(&self.mint),
+            ExplorerLink::Address(&self.metadata),
+        ]);
+        links
+    }
 }
 
-pub fn execute(args: CreateTokenMetadataArgs) -> Result<()> {
+pub fn execute(args: CreateTokenMetadataArgs, global: &GlobalArgs) -> Result<()> {
     let client = RpcClient::new(args.cluster.clone());
-    println!("Connected to {}", args.cluster);
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
 
-    let sender = crate::commands::load_keypair()?;
-    let token_mint = args.token_mint.parse()?;
+    let sender = crate::commands::load_keypair(global)?;
+    let output = run(&client, global, &sender, args)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
 
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    args: CreateTokenMetadataArgs,
+) -> Result<CreateTokenMetadataOutput> {
+    let token_mint = args.token_mint.parse()?;
     let sender_pubkey = sender.pubkey();
-    let (metadata_pda, _) = Metadata::find_pda(&token_mint);
-
-    let create_ix = CreateV1Builder::new()
-        .metadata(metadata_pda)
-        .mint(token_mint, false)
-        .authority(sender_pubkey)
-        .payer(sender_pubkey)
-        .update_authority(sender_pubkey, false)
-        .is_mutable(true)
-        .name(args.name)
-        .symbol(args.symbol)
-        .uri(args.uri)
-        .token_standard(TokenStandard::Fungible)
-        .seller_fee_basis_points(0)
-        .instruction();
-
-    let recent_blockhash = client.get_latest_blockhash()?;
-    let trx = solana_sdk::transaction::Transaction::new_signed_with_payer(
-        &[create_ix],
-        Some(&sender_pubkey),
-        &[&sender],
-        recent_blockhash,
-    );
-
-    let signature = client.send_and_confirm_transaction_with_spinner(&trx)?;
-    println!(
-        "✅ - Token metadata created, transaction signature: {}",
-        signature
-    );
-    println!("🔑 - Token mint address: {}", token_mint);
-
-    Ok(())
+
+    let (metadata_address, instructions) = if args.native_metadata {
+        (
+            token_mint,
+            native_metadata_instructions(client, &sender_pubkey, &token_mint, &args)?,
+        )
+    } else {
+        validate_metadata(&args.name, &args.symbol, &args.uri)?;
+        let (metadata_pda, _) = Metadata::find_pda(&token_mint);
+
+        let create_ix = CreateV1Builder::new()
+            .metadata(metadata_pda)
+            .mint(token_mint, false)
+            .authority(sender_pubkey)
+            .payer(sender_pubkey)
+            .update_authority(sender_pubkey, false)
+            .is_mutable(true)
+            .name(args.name.clone())
+            .symbol(args.symbol.clone())
+            .uri(args.uri.clone())
+            .token_standard(TokenStandard::Fungible)
+            .seller_fee_basis_points(0)
+            .instruction();
+        (metadata_pda, vec![create_ix])
+    };
+
+    let transaction =
+        process_transaction(client, global, &instructions, &sender_pubkey, &[sender])?;
+
+    Ok(CreateTokenMetadataOutput {
+        mint: token_mint.to_string(),
+        metadata: metadata_address.to_string(),
+        name: args.name,
+        symbol: args.symbol,
+        uri: args.uri,
+        transaction,
+    })
+}
+
+/// Initializes the token metadata extension of a Token-2022 mint, with the loaded keypair as
+/// mint and update authority
+fn native_metadata_instructions(
+    client: &RpcClient,
+    sender: &Pubkey,
+    mint: &Pubkey,
+    args: &CreateTokenMetadataArgs,
+) -> Result<Vec<Instruction>> {
+    let native_mint = fetch_native_metadata_mint(client, mint)?;
+    if native_mint.metadata.is_some() {
+        anyhow::bail!(
+            "Mint {} already has metadata, use update-token-metadata",
+            mint
+        );
+    }
+
+    let metadata = TokenMetadata {
+        update_authority: Some(*sender).try_into()?,
+        mint: *mint,
+        name: args.name.clone(),
+        symbol: args.symbol.clone(),
+        uri: args.uri.clone(),
+        additional_metadata: Vec::new(),
+    };
+
+    let mut instructions: Vec<Instruction> = native_mint
+        .rent_top_up(client, sender, mint, std::slice::from_ref(&metadata))?
+        .into_iter()
+        .collect();
+    instructions.push(spl_token_metadata_interface::instruction::initialize(
+        &spl_token_2022::ID,
+        mint,
+        sender,
+        mint,
+        sender,
+        metadata.name,
+        metadata.symbol,
+        metadata.uri,
+    ));
+    Ok(instructions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        account_response, assert_instructions, native_metadata_mint_account, MockRpc,
+    };
+    use clap::Parser;
+    use solana_client::rpc_request::RpcRequest;
+
+    #[test]
+    fn defaults_fit_metadata_limits() {
+        let cli = crate::cli::Cli::try_parse_from([
+            "rust-exercises",
+            "create-token-metadata",
+            "--token-mint",
+            &Pubkey::new_unique().to_string(),
+        ])
+        .unwrap();
+        let crate::cli::Commands::CreateTokenMetadata(args) = cli.command else {
+            panic!("expected create-token-metadata");
+        };
+
+        validate_metadata(&args.name, &args.symbol, &args.uri).unwrap();
+    }
+
+    #[test]
+    fn creates_fungible_metadata() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let args = CreateTokenMetadataArgs {
+            cluster: String::new(),
+            token_mint: mint.to_string(),
+            name: "Test".to_string(),
+            symbol: "TST".to_string(),
+            uri: "https://example.com/token.json".to_string(),
+            native_metadata: false,
+        };
+
+        run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        let expected = CreateV1Builder::new()
+            .metadata(Metadata::find_pda(&mint).0)
+            .mint(mint, false)
+            .authority(sender.pubkey())
+            .payer(sender.pubkey())
+            .update_authority(sender.pubkey(), false)
+            .is_mutable(true)
+            .name("Test".to_string())
+            .symbol("TST".to_string())
+            .uri("https://example.com/token.json".to_string())
+            .token_standard(TokenStandard::Fungible)
+            .seller_fee_basis_points(0)
+            .instruction();
+        assert_instructions(&rpc.sent_transaction(), &sender.pubkey(), &[expected]);
+    }
+
+    #[test]
+    fn initializes_native_metadata_in_mint() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let account = native_metadata_mint_account(&mint, &sender.pubkey(), None);
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&mint, &account),
+            )]
+            .into(),
+        );
+        let args = CreateTokenMetadataArgs {
+            cluster: String::new(),
+            token_mint: mint.to_string(),
+            name: "Test".to_string(),
+            symbol: "TST".to_string(),
+            uri: "https://example.com/token.json".to_string(),
+            native_metadata: true,
+        };
+
+        let output = run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        assert_eq!(output.metadata, mint.to_string());
+        // The mock RPC reports 20 lamports as the rent exempt minimum, the mint holds 1
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[
+                solana_sdk::system_instruction::transfer(&sender.pubkey(), &mint, 19),
+                spl_token_metadata_interface::instruction::initialize(
+                    &spl_token_2022::ID,
+                    &mint,
+                    &sender.pubkey(),
+                    &mint,
+                    &sender.pubkey(),
+                    "Test".to_string(),
+                    "TST".to_string(),
+                    "https://example.com/token.json".to_string(),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn native_metadata_needs_token_2022_mint() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let account =
+            crate::commands::test_utils::mint_account(&spl_token::ID, &sender.pubkey(), 2);
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&mint, &account),
+            )]
+            .into(),
+        );
+        let args = CreateTokenMetadataArgs {
+            cluster: String::new(),
+            token_mint: mint.to_string(),
+            name: "Test".to_string(),
+            symbol: "TST".to_string(),
+            uri: String::new(),
+            native_metadata: true,
+        };
+
+        assert!(run(&rpc.client, &GlobalArgs::default(), &sender, args).is_err());
+        assert!(rpc.sent_transactions().is_empty());
+    }
 }
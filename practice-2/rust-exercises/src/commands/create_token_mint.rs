@@ -1,10 +1,19 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::TokenProgramArgs;
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
 use anyhow::Result;
 use clap::Args;
+use serde::Serialize;
 use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{Keypair, Signer};
-use solana_sdk::transaction::Transaction;
 use spl_token::solana_program::program_pack::Pack;
 use spl_token::state::Mint;
+use spl_token_2022::extension::{
+    interest_bearing_mint, metadata_pointer, transfer_fee, ExtensionType,
+};
 
 #[derive(Args)]
 pub struct CreateTokenMintArgs {
@@ -12,53 +21,350 @@ pub struct CreateTokenMintArgs {
     pub cluster: String,
     #[clap(long, default_value = "2")]
     pub decimals: u8,
+    /// Mint authority, e.g. a multisig account (defaults to the loaded keypair)
+    #[clap(long)]
+    pub mint_authority: Option<String>,
+    /// Authority allowed to freeze token accounts, none when omitted
+    #[clap(long)]
+    pub freeze_authority: Option<String>,
+    #[clap(flatten)]
+    pub token_program: TokenProgramArgs,
+    #[clap(flatten)]
+    pub extensions: MintExtensionArgs,
 }
 
-pub fn execute(args: CreateTokenMintArgs) -> Result<()> {
+/// Token-2022 mint extensions, their authorities are the mint authority
+#[derive(Args, Default)]
+#[clap(about = None, long_about = None)]
+pub struct MintExtensionArgs {
+    /// Transfer fee in basis points of every transfer
+    #[clap(long, requires_all = ["token_2022", "max_fee"])]
+    pub transfer_fee_bps: Option<u16>,
+    /// Maximum transfer fee in base units
+    #[clap(long, requires = "transfer_fee_bps")]
+    pub max_fee: Option<u64>,
+    /// Interest rate in basis points, may be negative
+    #[clap(long, requires = "token_2022", allow_hyphen_values = true)]
+    pub interest_rate: Option<i16>,
+    /// Tokens can't be transferred once minted
+    #[clap(long, requires = "token_2022")]
+    pub non_transferable: bool,
+    /// Delegate allowed to transfer or burn tokens from any account
+    #[clap(long, requires = "token_2022")]
+    pub permanent_delegate: Option<String>,
+    /// Point the mint's metadata pointer at the mint itself
+    #[clap(long, requires = "token_2022")]
+    pub metadata_pointer: bool,
+}
+
+impl MintExtensionArgs {
+    /// Extensions to allocate space for and the instructions initializing them,
+    /// which have to run before the mint itself is initialized
+    pub fn instructions(
+        &self,
+        mint: &Pubkey,
+        authority: &Pubkey,
+    ) -> Result<(Vec<ExtensionType>, Vec<Instruction>)> {
+        let program_id = spl_token_2022::ID;
+        let mut extensions = Vec::new();
+        let mut instructions = Vec::new();
+
+        if let Some(transfer_fee_bps) = self.transfer_fee_bps {
+            extensions.push(ExtensionType::TransferFeeConfig);
+            instructions.push(transfer_fee::instruction::initialize_transfer_fee_config(
+                &program_id,
+                mint,
+                Some(authority),
+                Some(authority),
+                transfer_fee_bps,
+                self.max_fee.unwrap_or_default(),
+            )?);
+        }
+
+        if let Some(interest_rate) = self.interest_rate {
+            extensions.push(ExtensionType::InterestBearingConfig);
+            instructions.push(interest_bearing_mint::instruction::initialize(
+                &program_id,
+                mint,
+                Some(*authority),
+                interest_rate,
+            )?);
+        }
+
+        if self.non_transferable {
+            extensions.push(ExtensionType::NonTransferable);
+            instructions.push(
+                spl_token_2022::instruction::initialize_non_transferable_mint(&program_id, mint)?,
+            );
+        }
+
+        if let Some(delegate) = &self.permanent_delegate {
+            extensions.push(ExtensionType::PermanentDelegate);
+            instructions.push(spl_token_2022::instruction::initialize_permanent_delegate(
+                &program_id,
+                mint,
+                &delegate.parse()?,
+            )?);
+        }
+
+        if self.metadata_pointer {
+            extensions.push(ExtensionType::MetadataPointer);
+            instructions.push(metadata_pointer::instruction::initialize(
+                &program_id,
+                mint,
+                Some(*authority),
+                Some(*mint),
+            )?);
+        }
+
+        Ok((extensions, instructions))
+    }
+}
+
+#[derive(Serialize)]
+pub struct CreateTokenMintOutput {
+    pub mint: String,
+    pub mint_authority: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freeze_authority: Option<String>,
+    pub decimals: u8,
+    pub token_program: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<String>,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for CreateTokenMintOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Token mint created");
+        println!("🔑 - Token mint account: {}", self.mint);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([
+            ExplorerLink::Address(&self.mint),
+            ExplorerLink::Address(&self.mint_authority),
+        ]);
+        links.extend(self.freeze_authority.as_deref().map(ExplorerLink::Address));
+        links
+    }
+}
+
+pub fn execute(args: CreateTokenMintArgs, global: &GlobalArgs) -> Result<()> {
     let client = RpcClient::new(args.cluster.clone());
-    println!("Connected to {}", args.cluster);
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
 
-    let sender = crate::commands::load_keypair()?;
+    let sender = crate::commands::load_keypair(global)?;
+    let output = run(&client, global, &sender, args)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    args: CreateTokenMintArgs,
+) -> Result<CreateTokenMintOutput> {
     let mint_account = Keypair::new();
 
     let sender_pubkey = sender.pubkey();
     let mint_account_pubkey = mint_account.pubkey();
+    let mint_authority = match &args.mint_authority {
+        Some(mint_authority) => mint_authority.parse()?,
+        None => sender_pubkey,
+    };
+    let freeze_authority: Option<Pubkey> = args
+        .freeze_authority
+        .as_deref()
+        .map(str::parse)
+        .transpose()?;
+
+    let program_id = args.token_program.program_id();
+    let (extensions, mut instructions, mint_len) = if args.token_program.token_2022 {
+        let (extensions, extension_instructions) = args
+            .extensions
+            .instructions(&mint_account_pubkey, &mint_authority)?;
+        let mint_len =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)?;
+        (extensions, extension_instructions, mint_len)
+    } else {
+        (Vec::new(), Vec::new(), Mint::LEN)
+    };
 
     let minimum_balance_for_rent_exemption =
-        client.get_minimum_balance_for_rent_exemption(Mint::LEN)?;
+        client.get_minimum_balance_for_rent_exemption(mint_len)?;
 
     let create_account_instruction = solana_sdk::system_instruction::create_account(
         &sender_pubkey,
         &mint_account_pubkey,
         minimum_balance_for_rent_exemption,
-        Mint::LEN as u64,
-        &spl_token::ID,
+        mint_len as u64,
+        &program_id,
     );
+    instructions.insert(0, create_account_instruction);
 
-    let initialize_mint_instruction = spl_token::instruction::initialize_mint(
-        &spl_token::ID,
-        &mint_account_pubkey,
+    let initialize_mint_instruction = if args.token_program.token_2022 {
+        spl_token_2022::instruction::initialize_mint2(
+            &program_id,
+            &mint_account_pubkey,
+            &mint_authority,
+            freeze_authority.as_ref(),
+            args.decimals,
+        )?
+    } else {
+        spl_token::instruction::initialize_mint(
+            &spl_token::ID,
+            &mint_account_pubkey,
+            &mint_authority,
+            freeze_authority.as_ref(),
+            args.decimals,
+        )?
+    };
+    instructions.push(initialize_mint_instruction);
+
+    let transaction = process_transaction(
+        client,
+        global,
+        &instructions,
         &sender_pubkey,
-        None,
-        args.decimals,
+        &[&mint_account, sender],
     )?;
 
-    let recent_blockhash = client.get_latest_blockhash()?;
+    Ok(CreateTokenMintOutput {
+        mint: mint_account_pubkey.to_string(),
+        mint_authority: mint_authority.to_string(),
+        freeze_authority: freeze_authority.map(|authority| authority.to_string()),
+        decimals: args.decimals,
+        token_program: program_id.to_string(),
+        extensions: extensions
+            .iter()
+            .map(|extension| format!("{:?}", extension))
+            .collect(),
+        transaction,
+    })
+}
 
-    let trx = Transaction::new_signed_with_payer(
-        &[create_account_instruction, initialize_mint_instruction],
-        Some(&sender_pubkey),
-        &[&mint_account, &sender],
-        recent_blockhash,
-    );
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{assert_instructions, MockRpc};
 
-    let signature = client.send_and_confirm_transaction_with_spinner(&trx)?;
+    #[test]
+    fn creates_and_initializes_mint() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let args = CreateTokenMintArgs {
+            cluster: String::new(),
+            decimals: 6,
+            mint_authority: None,
+            freeze_authority: None,
+            token_program: TokenProgramArgs::default(),
+            extensions: MintExtensionArgs::default(),
+        };
 
-    println!(
-        "✅ - Token mint created, transaction signature: {}",
-        signature
-    );
-    println!("🔑 - Token mint account: {}", mint_account_pubkey);
+        let output = run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        let transaction = rpc.sent_transaction();
+        let mint = output.mint.parse().unwrap();
+        // The mock RPC reports 20 lamports as the rent exempt minimum
+        assert_instructions(
+            &transaction,
+            &sender.pubkey(),
+            &[
+                solana_sdk::system_instruction::create_account(
+                    &sender.pubkey(),
+                    &mint,
+                    20,
+                    Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint(
+                    &spl_token::ID,
+                    &mint,
+                    &sender.pubkey(),
+                    None,
+                    6,
+                )
+                .unwrap(),
+            ],
+        );
+    }
+
+    #[test]
+    fn initializes_token_2022_extensions_before_mint() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let delegate = Pubkey::new_unique();
+        let args = CreateTokenMintArgs {
+            cluster: String::new(),
+            decimals: 9,
+            mint_authority: None,
+            freeze_authority: None,
+            token_program: TokenProgramArgs { token_2022: true },
+            extensions: MintExtensionArgs {
+                transfer_fee_bps: Some(50),
+                max_fee: Some(5_000),
+                non_transferable: false,
+                permanent_delegate: Some(delegate.to_string()),
+                ..MintExtensionArgs::default()
+            },
+        };
+
+        let output = run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
 
-    Ok(())
+        let mint = output.mint.parse().unwrap();
+        let authority = sender.pubkey();
+        let mint_len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::PermanentDelegate,
+        ])
+        .unwrap();
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[
+                solana_sdk::system_instruction::create_account(
+                    &sender.pubkey(),
+                    &mint,
+                    20,
+                    mint_len as u64,
+                    &spl_token_2022::ID,
+                ),
+                transfer_fee::instruction::initialize_transfer_fee_config(
+                    &spl_token_2022::ID,
+                    &mint,
+                    Some(&authority),
+                    Some(&authority),
+                    50,
+                    5_000,
+                )
+                .unwrap(),
+                spl_token_2022::instruction::initialize_permanent_delegate(
+                    &spl_token_2022::ID,
+                    &mint,
+                    &delegate,
+                )
+                .unwrap(),
+                spl_token_2022::instruction::initialize_mint2(
+                    &spl_token_2022::ID,
+                    &mint,
+                    &authority,
+                    None,
+                    9,
+                )
+                .unwrap(),
+            ],
+        );
+        assert_eq!(
+            output.extensions,
+            ["TransferFeeConfig", "PermanentDelegate"]
+        );
+    }
 }
@@ -0,0 +1,742 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::{fetch_mint, parse_token_amount, SOL_DECIMALS};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::Message;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Bytes kept free in every batch for instructions the transaction helper may add, like
+/// advancing a durable nonce and setting the compute budget
+const RESERVED_TRANSACTION_BYTES: usize = 250;
+const MAX_COMPUTE_UNITS: u64 = 1_400_000;
+// Rough compute cost of the instructions a batch is made of
+const SOL_TRANSFER_UNITS: u64 = 300;
+const TOKEN_TRANSFER_UNITS: u64 = 6_500;
+const CREATE_ACCOUNT_UNITS: u64 = 30_000;
+/// Accounts per `getMultipleAccounts` request
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+#[derive(Args)]
+pub struct DistributeArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    /// CSV file with `address,amount` rows, an `address,amount` header is optional
+    #[clap(long, value_name = "PATH")]
+    pub csv: PathBuf,
+    /// Distribute tokens of this mint to the recipients' associated token accounts, SOL when
+    /// omitted
+    #[clap(long)]
+    pub mint: Option<String>,
+    /// Completed rows and their signatures, rows found there are skipped on the next run.
+    /// Rows are matched by row number, address and amount, so moving a row in the CSV pays it
+    /// again [default: <CSV>.state.json]
+    #[clap(long, value_name = "PATH")]
+    pub state: Option<PathBuf>,
+    /// Final report with the status of every row [default: <CSV>.report.csv]
+    #[clap(long, value_name = "PATH")]
+    pub report: Option<PathBuf>,
+}
+
+/// A row of the recipients CSV
+#[derive(Debug, PartialEq)]
+pub struct Recipient {
+    /// Line number in the CSV file
+    pub row: usize,
+    pub address: Pubkey,
+    pub amount: String,
+}
+
+/// Rows completed by earlier runs, so an interrupted distribution can be resumed
+#[derive(Serialize, Deserialize, Default)]
+pub struct DistributionState {
+    pub completed: Vec<CompletedRow>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CompletedRow {
+    pub row: usize,
+    pub address: String,
+    pub amount: String,
+    pub signature: String,
+}
+
+#[derive(Serialize)]
+pub struct DistributeOutput {
+    pub rows: usize,
+    pub completed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub report: String,
+    pub state: String,
+    pub transactions: Vec<TransactionOutcome>,
+}
+
+impl CommandOutput for DistributeOutput {
+    fn print_text(&self) {
+        for transaction in &self.transactions {
+            transaction.print_text("Batch sent");
+        }
+        println!(
+            "📦 - {} rows: {} completed, {} skipped as done before, {} failed",
+            self.rows, self.completed, self.skipped, self.failed
+        );
+        println!("📄 - Report saved to {}", self.report);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        self.transactions
+            .iter()
+            .flat_map(TransactionOutcome::explorer_links)
+            .collect()
+    }
+}
+
+// What is distributed, with the details the instructions need
+enum Asset {
+    Sol,
+    Token {
+        mint: Pubkey,
+        program_id: Pubkey,
+        decimals: u8,
+        source: Pubkey,
+    },
+}
+
+impl Asset {
+    fn decimals(&self) -> u8 {
+        match self {
+            Asset::Sol => SOL_DECIMALS,
+            Asset::Token { decimals, .. } => *decimals,
+        }
+    }
+}
+
+// Status of a row in the report
+#[derive(Clone)]
+enum RowStatus {
+    Completed(String),
+    PreviouslyCompleted(String),
+    Simulated,
+    Signed,
+    Failed(String),
+}
+
+pub fn execute(args: DistributeArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    let sender = crate::commands::load_keypair(global)?;
+    let output = run(&client, global, &sender, args)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    args: DistributeArgs,
+) -> Result<DistributeOutput> {
+    let sender_pubkey = sender.pubkey();
+    let state_path = args
+        .state
+        .clone()
+        .unwrap_or_else(|| args.csv.with_extension("state.json"));
+    let report_path = args
+        .report
+        .clone()
+        .unwrap_or_else(|| args.csv.with_extension("report.csv"));
+
+    let content = std::fs::read_to_string(&args.csv)
+        .with_context(|| format!("Failed to read {}", args.csv.display()))?;
+    let recipients = parse_recipients(&content)?;
+    let mut state = load_state(&state_path)?;
+
+    let asset = match &args.mint {
+        Some(mint) => {
+            let mint = mint.parse()?;
+            let mint_info = fetch_mint(client, &mint)?;
+            Asset::Token {
+                mint,
+                program_id: mint_info.program_id,
+                decimals: mint_info.decimals,
+                source: get_associated_token_address_with_program_id(
+                    &sender_pubkey,
+                    &mint,
+                    &mint_info.program_id,
+                ),
+            }
+        }
+        None => Asset::Sol,
+    };
+
+    // Every amount is checked before anything is sent
+    let amounts = recipients
+        .iter()
+        .map(|recipient| {
+            parse_token_amount(&recipient.amount, asset.decimals())
+                .with_context(|| format!("Invalid amount in row {}", recipient.row))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut statuses: Vec<Option<RowStatus>> = recipients
+        .iter()
+        .map(|recipient| {
+            state
+                .completed
+                .iter()
+                .find(|completed| {
+                    completed.row == recipient.row
+                        && completed.address == recipient.address.to_string()
+                        && completed.amount == recipient.amount
+                })
+                .map(|completed| RowStatus::PreviouslyCompleted(completed.signature.clone()))
+        })
+        .collect();
+    let pending: Vec<usize> = (0..recipients.len())
+        .filter(|&index| statuses[index].is_none())
+        .collect();
+
+    let missing_accounts = match &asset {
+        Asset::Sol => HashSet::new(),
+        Asset::Token {
+            mint, program_id, ..
+        } => {
+            let accounts: Vec<Pubkey> = pending
+                .iter()
+                .map(|&index| {
+                    get_associated_token_address_with_program_id(
+                        &recipients[index].address,
+                        mint,
+                        program_id,
+                    )
+                })
+                .collect();
+            find_missing_accounts(client, &accounts)?
+        }
+    };
+
+    let mut batches: Vec<(Vec<usize>, Vec<Instruction>)> = Vec::new();
+    let mut rows = Vec::new();
+    let mut instructions = Vec::new();
+    let mut units = 0;
+    // Missing accounts the batch being built creates
+    let mut created_accounts = HashSet::new();
+    let pay_row = |index: usize, created_accounts: &mut HashSet<Pubkey>| {
+        row_instructions(
+            &asset,
+            &sender_pubkey,
+            &recipients[index].address,
+            amounts[index],
+            &missing_accounts,
+            created_accounts,
+        )
+    };
+    for index in pending {
+        let (row_instructions, row_units) = pay_row(index, &mut created_accounts)?;
+
+        let mut candidate = instructions.clone();
+        candidate.extend(row_instructions.iter().cloned());
+        let fits = units + row_units <= MAX_COMPUTE_UNITS
+            && transaction_size(&candidate, &sender_pubkey)? + RESERVED_TRANSACTION_BYTES
+                <= PACKET_DATA_SIZE;
+        // A row that fits no batch on its own still gets one, rather than an empty batch
+        if fits || instructions.is_empty() {
+            instructions = candidate;
+            units += row_units;
+        } else {
+            batches.push((std::mem::take(&mut rows), instructions));
+            // Every batch creates the accounts it pays into, a failed earlier batch would
+            // leave them missing otherwise
+            created_accounts.clear();
+            (instructions, units) = pay_row(index, &mut created_accounts)?;
+        }
+        rows.push(index);
+    }
+    if !rows.is_empty() {
+        batches.push((rows, instructions));
+    }
+
+    let mut transactions = Vec::new();
+    for (rows, instructions) in batches {
+        global.output.status(format!(
+            "Sending rows {}",
+            rows.iter()
+                .map(|&index| recipients[index].row.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        let status =
+            match process_transaction(client, global, &instructions, &sender_pubkey, &[sender]) {
+                Ok(transaction) => {
+                    let status = match &transaction {
+                        TransactionOutcome::Sent { signature, .. } => {
+                            RowStatus::Completed(signature.clone())
+                        }
+                        TransactionOutcome::Simulated(_) => RowStatus::Simulated,
+                        TransactionOutcome::SignedOnly { .. } => RowStatus::Signed,
+                    };
+                    transactions.push(transaction);
+                    status
+                }
+                Err(error) => RowStatus::Failed(format!("{:#}", error)),
+            };
+
+        if let RowStatus::Completed(signature) = &status {
+            state
+                .completed
+                .extend(rows.iter().map(|&index| CompletedRow {
+                    row: recipients[index].row,
+                    address: recipients[index].address.to_string(),
+                    amount: recipients[index].amount.clone(),
+                    signature: signature.clone(),
+                }));
+            // Saved after every batch, so an interruption loses no progress
+            save_state(&state_path, &state)?;
+        }
+        for index in rows {
+            statuses[index] = Some(status.clone());
+        }
+    }
+
+    let statuses: Vec<RowStatus> = statuses
+        .into_iter()
+        .map(|status| status.expect("every row has a status"))
+        .collect();
+    write_report(&report_path, &recipients, &statuses)?;
+
+    let count = |matches: fn(&RowStatus) -> bool| statuses.iter().filter(|s| matches(s)).count();
+    Ok(DistributeOutput {
+        rows: recipients.len(),
+        completed: count(|status| matches!(status, RowStatus::Completed(_))),
+        skipped: count(|status| matches!(status, RowStatus::PreviouslyCompleted(_))),
+        failed: count(|status| matches!(status, RowStatus::Failed(_))),
+        report: report_path.display().to_string(),
+        state: state_path.display().to_string(),
+        transactions,
+    })
+}
+
+/// Parses `address,amount` rows, skipping blank lines and an optional header
+pub fn parse_recipients(content: &str) -> Result<Vec<Recipient>> {
+    let mut recipients = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let row = index + 1;
+        let line = line.trim();
+        if line.is_empty() || (row == 1 && line.to_lowercase().starts_with("address")) {
+            continue;
+        }
+
+        let Some((address, amount)) = line.split_once(',') else {
+            bail!("Row {} is not an address,amount pair", row);
+        };
+        recipients.push(Recipient {
+            row,
+            address: address
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid address in row {}", row))?,
+            amount: amount.trim().to_string(),
+        });
+    }
+    Ok(recipients)
+}
+
+/// Instructions paying one recipient and their estimated compute units, creating a missing
+/// token account unless `created_accounts` of the batch already hold it
+fn row_instructions(
+    asset: &Asset,
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+    missing_accounts: &HashSet<Pubkey>,
+    created_accounts: &mut HashSet<Pubkey>,
+) -> Result<(Vec<Instruction>, u64)> {
+    match asset {
+        Asset::Sol => Ok((
+            vec![system_instruction::transfer(sender, recipient, amount)],
+            SOL_TRANSFER_UNITS,
+        )),
+        Asset::Token {
+            mint,
+            program_id,
+            decimals,
+            source,
+        } => {
+            let destination =
+                get_associated_token_address_with_program_id(recipient, mint, program_id);
+            let mut instructions = Vec::new();
+            let mut units = TOKEN_TRANSFER_UNITS;
+            // Created once per batch, even when the wallet appears in several rows
+            if missing_accounts.contains(&destination) && created_accounts.insert(destination) {
+                instructions.push(create_associated_token_account_idempotent(
+                    sender, recipient, mint, program_id,
+                ));
+                units += CREATE_ACCOUNT_UNITS;
+            }
+            instructions.push(spl_token_2022::instruction::transfer_checked(
+                program_id,
+                source,
+                mint,
+                &destination,
+                sender,
+                &[],
+                amount,
+                *decimals,
+            )?);
+            Ok((instructions, units))
+        }
+    }
+}
+
+fn find_missing_accounts(client: &RpcClient, accounts: &[Pubkey]) -> Result<HashSet<Pubkey>> {
+    let mut missing = HashSet::new();
+    for chunk in accounts.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let found = client.get_multiple_accounts(chunk)?;
+        missing.extend(
+            chunk
+                .iter()
+                .zip(found)
+                .filter(|(_, account)| account.is_none())
+                .map(|(address, _)| *address),
+        );
+    }
+    Ok(missing)
+}
+
+/// Serialized size of a transaction with these instructions, signatures included
+fn transaction_size(instructions: &[Instruction], payer: &Pubkey) -> Result<usize> {
+    let transaction = Transaction::new_unsigned(Message::new(instructions, Some(payer)));
+    Ok(bincode::serialized_size(&transaction)? as usize)
+}
+
+fn load_state(path: &Path) -> Result<DistributionState> {
+    if !path.exists() {
+        return Ok(DistributionState::default());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read state file {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse state file {}", path.display()))
+}
+
+fn save_state(path: &Path, state: &DistributionState) -> Result<()> {
+    std::fs::write(path, serde_json::to_string_pretty(state)?)
+        .with_context(|| format!("Failed to write state file {}", path.display()))
+}
+
+fn write_report(path: &Path, recipients: &[Recipient], statuses: &[RowStatus]) -> Result<()> {
+    let mut report = String::from("row,address,amount,status,signature,error\n");
+    for (recipient, status) in recipients.iter().zip(statuses) {
+        let (status, signature, error) = match status {
+            RowStatus::Completed(signature) => ("completed", signature.as_str(), ""),
+            RowStatus::PreviouslyCompleted(signature) => {
+                ("previously_completed", signature.as_str(), "")
+            }
+            RowStatus::Simulated => ("simulated", "", ""),
+            RowStatus::Signed => ("signed", "", ""),
+            RowStatus::Failed(error) => ("failed", "", error.as_str()),
+        };
+        report.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            recipient.row,
+            recipient.address,
+            csv_field(&recipient.amount),
+            status,
+            signature,
+            csv_field(error)
+        ));
+    }
+    std::fs::write(path, report)
+        .with_context(|| format!("Failed to write report {}", path.display()))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{account_response, mint_account, MockRpc};
+    use serde_json::json;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_client::rpc_response::{Response, RpcResponseContext};
+
+    // Empty directory for the files of one test, removed once the test ends
+    struct TempDir(PathBuf);
+
+    impl std::ops::Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn temp_dir() -> TempDir {
+        let dir = std::env::temp_dir().join(format!(
+            "distribute-{}-{}",
+            std::process::id(),
+            Pubkey::new_unique()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+
+    fn args(csv: PathBuf, mint: Option<&Pubkey>) -> DistributeArgs {
+        DistributeArgs {
+            cluster: String::new(),
+            csv,
+            mint: mint.map(Pubkey::to_string),
+            state: None,
+            report: None,
+        }
+    }
+
+    #[test]
+    fn parses_rows_and_skips_header() {
+        let address = Pubkey::new_unique();
+        let content = format!("address,amount\n{}, 1.5\n\n", address);
+
+        assert_eq!(
+            parse_recipients(&content).unwrap(),
+            [Recipient {
+                row: 2,
+                address,
+                amount: "1.5".to_string(),
+            }]
+        );
+        assert!(parse_recipients("not-an-address,1").is_err());
+        assert!(parse_recipients(&address.to_string()).is_err());
+    }
+
+    #[test]
+    fn packs_transfers_into_batches_and_resumes() {
+        let dir = temp_dir();
+        let csv = dir.join("recipients.csv");
+        let recipients: Vec<Pubkey> = (0..40).map(|_| Pubkey::new_unique()).collect();
+        let content: String = recipients
+            .iter()
+            .map(|recipient| format!("{},0.001\n", recipient))
+            .collect();
+        std::fs::write(&csv, content).unwrap();
+        let sender = Keypair::new();
+
+        let rpc = MockRpc::new();
+        let output = run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            args(csv.clone(), None),
+        )
+        .unwrap();
+
+        let transactions = rpc.sent_transactions();
+        assert!(transactions.len() > 1);
+        let transfers: usize = transactions
+            .iter()
+            .map(|transaction| {
+                assert!(
+                    bincode::serialized_size(transaction).unwrap() as usize <= PACKET_DATA_SIZE
+                );
+                transaction.message.instructions.len()
+            })
+            .sum();
+        assert_eq!(transfers, 40);
+        assert_eq!(output.completed, 40);
+
+        let report = std::fs::read_to_string(dir.join("recipients.report.csv")).unwrap();
+        assert_eq!(report.lines().count(), 41);
+        assert!(report.lines().nth(1).unwrap().contains(",completed,"));
+
+        // A second run finds every row in the state file
+        let rpc = MockRpc::new();
+        let output = run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            args(csv, None),
+        )
+        .unwrap();
+
+        assert!(rpc.sent_transactions().is_empty());
+        assert_eq!(output.skipped, 40);
+        assert_eq!(output.completed, 0);
+    }
+
+    #[test]
+    fn creates_missing_token_accounts_once() {
+        let dir = temp_dir();
+        let csv = dir.join("recipients.csv");
+        let recipient = Pubkey::new_unique();
+        std::fs::write(&csv, format!("{},1\n{},2.5\n", recipient, recipient)).unwrap();
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let mint_response =
+            account_response(&mint, &mint_account(&spl_token::ID, &sender.pubkey(), 2));
+        let missing = json!(Response {
+            context: RpcResponseContext {
+                slot: 1,
+                api_version: None
+            },
+            value: vec![serde_json::Value::Null, serde_json::Value::Null],
+        });
+        let rpc = MockRpc::with_mocks(
+            [
+                (RpcRequest::GetAccountInfo, mint_response),
+                (RpcRequest::GetMultipleAccounts, missing),
+            ]
+            .into(),
+        );
+
+        run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            args(csv, Some(&mint)),
+        )
+        .unwrap();
+
+        let source =
+            spl_associated_token_account::get_associated_token_address(&sender.pubkey(), &mint);
+        let destination =
+            spl_associated_token_account::get_associated_token_address(&recipient, &mint);
+        let transfer = |amount| {
+            spl_token::instruction::transfer_checked(
+                &spl_token::ID,
+                &source,
+                &mint,
+                &destination,
+                &sender.pubkey(),
+                &[],
+                amount,
+                2,
+            )
+            .unwrap()
+        };
+        crate::commands::test_utils::assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[
+                create_associated_token_account_idempotent(
+                    &sender.pubkey(),
+                    &recipient,
+                    &mint,
+                    &spl_token::ID,
+                ),
+                transfer(100),
+                transfer(250),
+            ],
+        );
+    }
+
+    #[test]
+    fn creates_missing_token_account_in_every_batch() {
+        let dir = temp_dir();
+        let csv = dir.join("recipients.csv");
+        let recipient = Pubkey::new_unique();
+        // One lookup request holds at most 100 accounts
+        let rows = MAX_MULTIPLE_ACCOUNTS;
+        std::fs::write(&csv, format!("{},1\n", recipient).repeat(rows)).unwrap();
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let mint_response =
+            account_response(&mint, &mint_account(&spl_token::ID, &sender.pubkey(), 2));
+        let missing = json!(Response {
+            context: RpcResponseContext {
+                slot: 1,
+                api_version: None
+            },
+            value: vec![serde_json::Value::Null; rows],
+        });
+        let rpc = MockRpc::with_mocks(
+            [
+                (RpcRequest::GetAccountInfo, mint_response),
+                (RpcRequest::GetMultipleAccounts, missing),
+            ]
+            .into(),
+        );
+
+        run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            args(csv, Some(&mint)),
+        )
+        .unwrap();
+
+        let create = create_associated_token_account_idempotent(
+            &sender.pubkey(),
+            &recipient,
+            &mint,
+            &spl_token::ID,
+        );
+        let transactions = rpc.sent_transactions();
+        assert!(transactions.len() > 1);
+        for transaction in transactions {
+            let message = &transaction.message;
+            let creates = message
+                .instructions
+                .iter()
+                .filter(|instruction| {
+                    message.account_keys[instruction.program_id_index as usize] == create.program_id
+                })
+                .count();
+            assert_eq!(creates, 1);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_amount_before_sending() {
+        let dir = temp_dir();
+        let csv = dir.join("recipients.csv");
+        std::fs::write(
+            &csv,
+            format!(
+                "{},1\n{},lots\n",
+                Pubkey::new_unique(),
+                Pubkey::new_unique()
+            ),
+        )
+        .unwrap();
+
+        let rpc = MockRpc::new();
+        assert!(run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &Keypair::new(),
+            args(csv, None)
+        )
+        .is_err());
+        assert!(rpc.sent_transactions().is_empty());
+    }
+}
@@ -0,0 +1,385 @@
+use crate::cli::GlobalArgs;
+use crate::commands::anchor::{discriminator, ANCHOR_DISCRIMINATOR_SIZE, FAVORITES_PROGRAM};
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
+use anyhow::{bail, Context, Result};
+use borsh::{BorshDeserialize, BorshSerialize};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcProgramAccountsConfig;
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::str::FromStr;
+
+const FAVORITES_SEED: &[u8] = b"favorites";
+/// `#[max_len]` of the color, in bytes, the program allocates for
+pub const MAX_COLOR_LEN: usize = 50;
+
+#[derive(Args)]
+pub struct FavoritesArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    #[clap(subcommand)]
+    pub command: FavoritesCommand,
+}
+
+#[derive(Subcommand)]
+pub enum FavoritesCommand {
+    /// Set favorites for the loaded keypair
+    Set {
+        #[clap(long)]
+        number: u64,
+        #[clap(long)]
+        color: String,
+    },
+    /// Show favorites of a user (defaults to the loaded keypair)
+    Get {
+        #[clap(long)]
+        user: Option<String>,
+    },
+    /// List all favorites accounts owned by the program
+    List,
+}
+
+/// Mirrors the `Favorites` account of the favorites Anchor program.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Debug, PartialEq)]
+pub struct Favorites {
+    pub number: u64,
+    pub color: String,
+}
+
+#[derive(BorshSerialize)]
+struct SetFavoritesData {
+    number: u64,
+    color: String,
+}
+
+#[derive(Serialize)]
+pub struct SetFavoritesOutput {
+    pub favorites_account: String,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for SetFavoritesOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Favorites set");
+        println!("🔑 - Favorites account: {}", self.favorites_account);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([ExplorerLink::Address(&self.favorites_account)]);
+        links
+    }
+}
+
+#[derive(Serialize)]
+pub struct FavoritesEntry {
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(flatten)]
+    pub favorites: Favorites,
+}
+
+impl CommandOutput for FavoritesEntry {
+    fn print_text(&self) {
+        println!("🔑 - Favorites account: {}", self.address);
+        if let Some(user) = &self.user {
+            println!("👤 - User: {}", user);
+        }
+        println!("🔢 - Number: {}", self.favorites.number);
+        println!("🎨 - Color: {}", self.favorites.color);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = vec![ExplorerLink::Address(&self.address)];
+        links.extend(self.user.as_deref().map(ExplorerLink::Address));
+        links
+    }
+}
+
+#[derive(Serialize)]
+pub struct FavoritesList {
+    pub accounts: Vec<FavoritesEntry>,
+}
+
+impl CommandOutput for FavoritesList {
+    fn print_text(&self) {
+        println!("📋 - Found {} favorites accounts", self.accounts.len());
+        self.accounts.iter().for_each(FavoritesEntry::print_text);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        self.accounts
+            .iter()
+            .flat_map(FavoritesEntry::explorer_links)
+            .collect()
+    }
+}
+
+pub fn program_id() -> Pubkey {
+    Pubkey::from_str(FAVORITES_PROGRAM).expect("valid favorites program id")
+}
+
+pub fn find_favorites_pda(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FAVORITES_SEED, user.as_ref()], &program_id())
+}
+
+pub fn account_discriminator() -> [u8; ANCHOR_DISCRIMINATOR_SIZE] {
+    discriminator("account", "Favorites")
+}
+
+pub fn set_favorites_instruction(user: &Pubkey, number: u64, color: String) -> Result<Instruction> {
+    if color.len() > MAX_COLOR_LEN {
+        bail!(
+            "Color is {} bytes long, the favorites program stores at most {}",
+            color.len(),
+            MAX_COLOR_LEN
+        );
+    }
+    let (favorites_pda, _) = find_favorites_pda(user);
+
+    let mut data = discriminator("global", "set_favorites").to_vec();
+    SetFavoritesData { number, color }.serialize(&mut data)?;
+
+    Ok(Instruction::new_with_bytes(
+        program_id(),
+        &data,
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(favorites_pda, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        ],
+    ))
+}
+
+pub fn decode_favorites(data: &[u8]) -> Result<Favorites> {
+    if data.len() < ANCHOR_DISCRIMINATOR_SIZE
+        || data[..ANCHOR_DISCRIMINATOR_SIZE] != account_discriminator()
+    {
+        bail!("Account is not a favorites account");
+    }
+
+    // Accounts are allocated with the maximum color length, so trailing bytes are expected
+    let mut payload = &data[ANCHOR_DISCRIMINATOR_SIZE..];
+    Favorites::deserialize(&mut payload).context("Failed to decode favorites account")
+}
+
+pub fn execute(args: FavoritesArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    match args.command {
+        FavoritesCommand::Set { number, color } => {
+            let sender = crate::commands::load_keypair(global)?;
+            let output = set(&client, global, &sender, number, color)?;
+            global
+                .output
+                .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+        }
+        FavoritesCommand::Get { user } => {
+            let user = match user {
+                Some(user) => user.parse()?,
+                None => crate::commands::load_keypair(global)?.pubkey(),
+            };
+            global.output.print_with_links(
+                &ExplorerLinks::new(global.explorer, &client.url()),
+                &get(&client, &user)?,
+            )
+        }
+        FavoritesCommand::List => global.output.print_with_links(
+            &ExplorerLinks::new(global.explorer, &client.url()),
+            &list(&client)?,
+        ),
+    }
+}
+
+pub fn set(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    number: u64,
+    color: String,
+) -> Result<SetFavoritesOutput> {
+    let sender_pubkey = sender.pubkey();
+    let (favorites_pda, _) = find_favorites_pda(&sender_pubkey);
+
+    let instruction = set_favorites_instruction(&sender_pubkey, number, color)?;
+
+    let transaction =
+        process_transaction(client, global, &[instruction], &sender_pubkey, &[sender])?;
+
+    Ok(SetFavoritesOutput {
+        favorites_account: favorites_pda.to_string(),
+        transaction,
+    })
+}
+
+pub fn get(client: &RpcClient, user: &Pubkey) -> Result<FavoritesEntry> {
+    let (favorites_pda, _) = find_favorites_pda(user);
+
+    // RPC failures are reported as such, only a missing account means no favorites
+    let Some(account) = client
+        .get_account_with_commitment(&favorites_pda, client.commitment())?
+        .value
+    else {
+        bail!("No favorites found for {}", user);
+    };
+
+    Ok(FavoritesEntry {
+        address: favorites_pda.to_string(),
+        user: Some(user.to_string()),
+        favorites: decode_favorites(&account.data)?,
+    })
+}
+
+pub fn list(client: &RpcClient) -> Result<FavoritesList> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            account_discriminator().to_vec(),
+        ))]),
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    let accounts = client.get_program_accounts_with_config(&program_id(), config)?;
+    let accounts = accounts
+        .into_iter()
+        .map(|(address, account)| {
+            Ok(FavoritesEntry {
+                address: address.to_string(),
+                user: None,
+                favorites: decode_favorites(&account.data)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(FavoritesList { accounts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{account_response, assert_instructions, MockRpc};
+    use solana_client::rpc_request::RpcRequest;
+    use solana_sdk::account::Account;
+
+    #[test]
+    fn sets_favorites() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+
+        set(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            7,
+            "blue".to_string(),
+        )
+        .unwrap();
+
+        let mut data = discriminator("global", "set_favorites").to_vec();
+        data.extend_from_slice(&7u64.to_le_bytes());
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(b"blue");
+        let expected = Instruction::new_with_bytes(
+            program_id(),
+            &data,
+            vec![
+                AccountMeta::new(sender.pubkey(), true),
+                AccountMeta::new(find_favorites_pda(&sender.pubkey()).0, false),
+                AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+            ],
+        );
+        assert_instructions(&rpc.sent_transaction(), &sender.pubkey(), &[expected]);
+    }
+
+    #[test]
+    fn decodes_favorites_account() {
+        let favorites = Favorites {
+            number: 42,
+            color: "red".to_string(),
+        };
+        let mut data = account_discriminator().to_vec();
+        BorshSerialize::serialize(&favorites, &mut data).unwrap();
+        // Space reserved for the longest color
+        data.resize(8 + 8 + 4 + MAX_COLOR_LEN, 0);
+
+        assert_eq!(decode_favorites(&data).unwrap(), favorites);
+
+        assert!(decode_favorites(&data[8..]).is_err());
+    }
+
+    #[test]
+    fn rejects_colors_longer_than_the_account_holds() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+
+        // Multibyte characters count with their encoded length
+        let error = set(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            7,
+            "é".repeat(26),
+        )
+        .err()
+        .unwrap();
+
+        assert!(error.to_string().contains("at most 50"));
+        assert!(rpc.sent_transactions().is_empty());
+        assert!(set_favorites_instruction(&sender.pubkey(), 7, "a".repeat(MAX_COLOR_LEN)).is_ok());
+    }
+
+    #[test]
+    fn gets_favorites_of_user() {
+        let user = Pubkey::new_unique();
+        let (favorites_pda, _) = find_favorites_pda(&user);
+        let mut data = account_discriminator().to_vec();
+        let favorites = Favorites {
+            number: 3,
+            color: "green".to_string(),
+        };
+        BorshSerialize::serialize(&favorites, &mut data).unwrap();
+        let account = Account {
+            lamports: 1,
+            data,
+            owner: program_id(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&favorites_pda, &account),
+            )]
+            .into(),
+        );
+
+        let entry = get(&rpc.client, &user).unwrap();
+
+        assert_eq!(entry.address, favorites_pda.to_string());
+        assert_eq!(entry.user, Some(user.to_string()));
+        assert_eq!(entry.favorites, favorites);
+    }
+
+    #[test]
+    fn reports_missing_favorites() {
+        let rpc = MockRpc::new();
+        let user = Pubkey::new_unique();
+
+        let error = get(&rpc.client, &user).err().unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            format!("No favorites found for {}", user)
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::{fetch_mint, resolve_token_account};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[derive(Args)]
+pub struct FreezeAccountArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    #[clap(long)]
+    pub mint: String,
+    /// Wallet whose associated token account is targeted, defaults to the loaded keypair
+    #[clap(long, conflicts_with = "token_account")]
+    pub owner: Option<String>,
+    /// Token account to target instead of an associated token account
+    #[clap(long)]
+    pub token_account: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FreezeAction {
+    Freeze,
+    Thaw,
+}
+
+#[derive(Serialize)]
+pub struct FreezeAccountOutput {
+    pub mint: String,
+    pub token_account: String,
+    pub frozen: bool,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for FreezeAccountOutput {
+    fn print_text(&self) {
+        if self.frozen {
+            self.transaction.print_text("Token account frozen");
+        } else {
+            self.transaction.print_text("Token account thawed");
+        }
+        println!("🔑 - Token account: {}", self.token_account);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([
+            ExplorerLink::Address(&self.mint),
+            ExplorerLink::Address(&self.token_account),
+        ]);
+        links
+    }
+}
+
+pub fn execute(args: FreezeAccountArgs, global: &GlobalArgs, action: FreezeAction) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    let sender = crate::commands::load_keypair(global)?;
+    let output = run(&client, global, &sender, args, action)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+/// Freezes or thaws a token account, signed by the loaded keypair as freeze authority
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    args: FreezeAccountArgs,
+    action: FreezeAction,
+) -> Result<FreezeAccountOutput> {
+    let sender_pubkey = sender.pubkey();
+    let mint: Pubkey = args.mint.parse()?;
+    let owner = match &args.owner {
+        Some(owner) => owner.parse()?,
+        None => sender_pubkey,
+    };
+
+    let mint_info = fetch_mint(client, &mint)?;
+    let token_account = resolve_token_account(
+        args.token_account.as_deref(),
+        &owner,
+        &mint,
+        &mint_info.program_id,
+    )?;
+
+    let instruction = match action {
+        FreezeAction::Freeze => spl_token_2022::instruction::freeze_account(
+            &mint_info.program_id,
+            &token_account,
+            &mint,
+            &sender_pubkey,
+            &[],
+        )?,
+        FreezeAction::Thaw => spl_token_2022::instruction::thaw_account(
+            &mint_info.program_id,
+            &token_account,
+            &mint,
+            &sender_pubkey,
+            &[],
+        )?,
+    };
+
+    let transaction =
+        process_transaction(client, global, &[instruction], &sender_pubkey, &[sender])?;
+
+    Ok(FreezeAccountOutput {
+        mint: mint.to_string(),
+        token_account: token_account.to_string(),
+        frozen: action == FreezeAction::Freeze,
+        transaction,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        account_response, assert_instructions, mint_account, MockRpc,
+    };
+    use solana_client::rpc_request::RpcRequest;
+
+    fn rpc_with_mint(mint: &Pubkey, authority: &Pubkey) -> MockRpc {
+        let account = mint_account(&spl_token_2022::ID, authority, 0);
+        MockRpc::with_mocks([(RpcRequest::GetAccountInfo, account_response(mint, &account))].into())
+    }
+
+    fn args(mint: &Pubkey, owner: &Pubkey) -> FreezeAccountArgs {
+        FreezeAccountArgs {
+            cluster: String::new(),
+            mint: mint.to_string(),
+            owner: Some(owner.to_string()),
+            token_account: None,
+        }
+    }
+
+    #[test]
+    fn freezes_and_thaws_owner_account() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let token_account =
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                &owner,
+                &mint,
+                &spl_token_2022::ID,
+            );
+
+        let rpc = rpc_with_mint(&mint, &sender.pubkey());
+        let output = run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            args(&mint, &owner),
+            FreezeAction::Freeze,
+        )
+        .unwrap();
+        assert!(output.frozen);
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[spl_token_2022::instruction::freeze_account(
+                &spl_token_2022::ID,
+                &token_account,
+                &mint,
+                &sender.pubkey(),
+                &[],
+            )
+            .unwrap()],
+        );
+
+        let rpc = rpc_with_mint(&mint, &sender.pubkey());
+        run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            args(&mint, &owner),
+            FreezeAction::Thaw,
+        )
+        .unwrap();
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[spl_token_2022::instruction::thaw_account(
+                &spl_token_2022::ID,
+                &token_account,
+                &mint,
+                &sender.pubkey(),
+                &[],
+            )
+            .unwrap()],
+        );
+    }
+}
@@ -0,0 +1,688 @@
+use crate::cli::GlobalArgs;
+use crate::commands::anchor::{
+    self, ANCHOR_DISCRIMINATOR_SIZE, ESCROW_APPROVE_PROGRAM, ESCROW_PROGRAM,
+};
+use crate::commands::favorites;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::format_token_amount;
+use crate::commands::token_metadata::decode_metadata;
+use anyhow::{Context, Result};
+use borsh::BorshDeserialize;
+use clap::Args;
+use mpl_token_metadata::types::Key;
+use serde::Serialize;
+use solana_client::nonce_utils;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::program_option::COption;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+
+/// Borsh size of the escrow_approve `Offer`, without the discriminator
+const APPROVE_ESCROW_OFFER_LEN: usize = 8 + 32 * 3 + 8 + 8 + 1;
+
+#[derive(Args)]
+pub struct InspectArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    /// Address of the account to decode
+    pub address: String,
+}
+
+#[derive(Serialize)]
+pub struct InspectOutput {
+    pub address: String,
+    pub owner: String,
+    pub lamports: u64,
+    pub executable: bool,
+    pub data_len: usize,
+    #[serde(flatten)]
+    pub details: AccountDetails,
+}
+
+/// Decoded contents of an account, by the type detected from its owner and data
+#[derive(Serialize)]
+#[serde(tag = "account_type", rename_all = "snake_case")]
+pub enum AccountDetails {
+    Wallet,
+    Program,
+    Nonce {
+        authority: String,
+        nonce: String,
+        lamports_per_signature: u64,
+    },
+    Mint {
+        program: String,
+        mint_authority: Option<String>,
+        freeze_authority: Option<String>,
+        supply: String,
+        decimals: u8,
+        extensions: Vec<String>,
+    },
+    TokenAccount {
+        program: String,
+        mint: String,
+        owner: String,
+        /// Amount in base units
+        amount: u64,
+        state: String,
+        delegate: Option<String>,
+        close_authority: Option<String>,
+        extensions: Vec<String>,
+    },
+    Multisig {
+        program: String,
+        threshold: u8,
+        signers: Vec<String>,
+    },
+    Metadata {
+        mint: String,
+        update_authority: String,
+        name: String,
+        symbol: String,
+        uri: String,
+        seller_fee_basis_points: u16,
+        is_mutable: bool,
+    },
+    Offer {
+        id: u64,
+        maker: String,
+        token_mint_a: String,
+        token_mint_b: String,
+        /// Only recorded by the escrow_approve program
+        #[serde(skip_serializing_if = "Option::is_none")]
+        token_a_offered_amount: Option<u64>,
+        token_b_wanted_amount: u64,
+        bump: u8,
+    },
+    Favorites {
+        number: u64,
+        color: String,
+    },
+    Unknown,
+}
+
+/// Mirrors the `Offer` account of the escrow Anchor program.
+#[derive(BorshDeserialize)]
+struct EscrowOffer {
+    id: u64,
+    maker: Pubkey,
+    token_mint_a: Pubkey,
+    token_mint_b: Pubkey,
+    token_b_wanted_amount: u64,
+    bump: u8,
+}
+
+/// Mirrors the `Offer` account of the escrow_approve Anchor program.
+#[derive(BorshDeserialize)]
+struct ApproveEscrowOffer {
+    id: u64,
+    maker: Pubkey,
+    token_mint_a: Pubkey,
+    token_mint_b: Pubkey,
+    token_a_offered_amount: u64,
+    token_b_wanted_amount: u64,
+    bump: u8,
+}
+
+impl CommandOutput for InspectOutput {
+    fn print_text(&self) {
+        println!("🔑 - Address: {}", self.address);
+        println!("👤 - Owner program: {}", self.owner);
+        println!(
+            "💰 - Balance: {} SOL ({} lamports)",
+            lamports_to_sol(self.lamports),
+            self.lamports
+        );
+        println!("📦 - Data: {} bytes", self.data_len);
+        self.details.print_text();
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = vec![
+            ExplorerLink::Address(&self.address),
+            ExplorerLink::Address(&self.owner),
+        ];
+        links.extend(self.details.explorer_links());
+        links
+    }
+}
+
+impl AccountDetails {
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let addresses: Vec<&String> = match self {
+            AccountDetails::Nonce { authority, .. } => vec![authority],
+            AccountDetails::Mint {
+                mint_authority,
+                freeze_authority,
+                ..
+            } => mint_authority.iter().chain(freeze_authority).collect(),
+            AccountDetails::TokenAccount {
+                mint,
+                owner,
+                delegate,
+                close_authority,
+                ..
+            } => [mint, owner]
+                .into_iter()
+                .chain(delegate)
+                .chain(close_authority)
+                .collect(),
+            AccountDetails::Multisig { signers, .. } => signers.iter().collect(),
+            AccountDetails::Metadata {
+                mint,
+                update_authority,
+                ..
+            } => vec![mint, update_authority],
+            AccountDetails::Offer {
+                maker,
+                token_mint_a,
+                token_mint_b,
+                ..
+            } => vec![maker, token_mint_a, token_mint_b],
+            AccountDetails::Wallet
+            | AccountDetails::Program
+            | AccountDetails::Favorites { .. }
+            | AccountDetails::Unknown => Vec::new(),
+        };
+        addresses
+            .into_iter()
+            .map(|address| ExplorerLink::Address(address))
+            .collect()
+    }
+
+    fn print_text(&self) {
+        match self {
+            AccountDetails::Wallet => println!("🧾 - Type: system wallet"),
+            AccountDetails::Program => println!("🧾 - Type: executable program"),
+            AccountDetails::Nonce {
+                authority,
+                nonce,
+                lamports_per_signature,
+            } => {
+                println!("🧾 - Type: durable nonce account");
+                println!("👤 - Nonce authority: {}", authority);
+                println!("🔢 - Nonce: {}", nonce);
+                println!("💸 - Lamports per signature: {}", lamports_per_signature);
+            }
+            AccountDetails::Mint {
+                program,
+                mint_authority,
+                freeze_authority,
+                supply,
+                decimals,
+                extensions,
+            } => {
+                println!("🧾 - Type: token mint of {}", program);
+                println!("👤 - Mint authority: {}", or_none(mint_authority));
+                println!("🧊 - Freeze authority: {}", or_none(freeze_authority));
+                println!("💰 - Supply: {}", supply);
+                println!("🔢 - Decimals: {}", decimals);
+                print_extensions(extensions);
+            }
+            AccountDetails::TokenAccount {
+                program,
+                mint,
+                owner,
+                amount,
+                state,
+                delegate,
+                close_authority,
+                extensions,
+            } => {
+                println!("🧾 - Type: token account of {}", program);
+                println!("🪙 - Mint: {}", mint);
+                println!("👤 - Owner: {}", owner);
+                println!("💰 - Amount: {} base units", amount);
+                println!("🚦 - State: {}", state);
+                println!("🤝 - Delegate: {}", or_none(delegate));
+                println!("🗑️ - Close authority: {}", or_none(close_authority));
+                print_extensions(extensions);
+            }
+            AccountDetails::Multisig {
+                program,
+                threshold,
+                signers,
+            } => {
+                println!("🧾 - Type: token multisig of {}", program);
+                println!("✍️ - Threshold: {} of {}", threshold, signers.len());
+                for signer in signers {
+                    println!("    {}", signer);
+                }
+            }
+            AccountDetails::Metadata {
+                mint,
+                update_authority,
+                name,
+                symbol,
+                uri,
+                seller_fee_basis_points,
+                is_mutable,
+            } => {
+                println!("🧾 - Type: Metaplex token metadata");
+                println!("🪙 - Mint: {}", mint);
+                println!("🏷️ - Name: {}", name);
+                println!("🔤 - Symbol: {}", symbol);
+                println!("🔗 - URI: {}", uri);
+                println!("👤 - Update authority: {}", update_authority);
+                println!("💸 - Seller fee: {} bps", seller_fee_basis_points);
+                println!("✏️ - Mutable: {}", is_mutable);
+            }
+            AccountDetails::Offer {
+                id,
+                maker,
+                token_mint_a,
+                token_mint_b,
+                token_a_offered_amount,
+                token_b_wanted_amount,
+                bump,
+            } => {
+                println!("🧾 - Type: escrow offer");
+                println!("🔢 - Id: {}", id);
+                println!("👤 - Maker: {}", maker);
+                println!("🪙 - Token mint A: {}", token_mint_a);
+                if let Some(amount) = token_a_offered_amount {
+                    println!("📤 - Token A offered: {} base units", amount);
+                }
+                println!("🪙 - Token mint B: {}", token_mint_b);
+                println!("📥 - Token B wanted: {} base units", token_b_wanted_amount);
+                println!("🌱 - Bump: {}", bump);
+            }
+            AccountDetails::Favorites { number, color } => {
+                println!("🧾 - Type: favorites");
+                println!("🔢 - Number: {}", number);
+                println!("🎨 - Color: {}", color);
+            }
+            AccountDetails::Unknown => println!("🧾 - Type: unknown"),
+        }
+    }
+}
+
+fn or_none(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("none")
+}
+
+fn print_extensions(extensions: &[String]) {
+    if !extensions.is_empty() {
+        println!("🧩 - Extensions: {}", extensions.join(", "));
+    }
+}
+
+pub fn execute(args: InspectArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    let output = run(&client, &args.address.parse()?)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+pub fn run(client: &RpcClient, address: &Pubkey) -> Result<InspectOutput> {
+    let account = client
+        .get_account(address)
+        .with_context(|| format!("Failed to fetch account {}", address))?;
+
+    Ok(InspectOutput {
+        address: address.to_string(),
+        owner: account.owner.to_string(),
+        lamports: account.lamports,
+        executable: account.executable,
+        data_len: account.data.len(),
+        details: decode_account(&account)?,
+    })
+}
+
+/// Detects the type of `account` from its owner program and data and decodes it
+pub fn decode_account(account: &Account) -> Result<AccountDetails> {
+    if account.executable {
+        return Ok(AccountDetails::Program);
+    }
+
+    let owner = account.owner;
+    if owner == solana_sdk::system_program::ID {
+        if account.data.is_empty() {
+            return Ok(AccountDetails::Wallet);
+        }
+        if let Ok(nonce) = nonce_utils::data_from_account(account) {
+            return Ok(AccountDetails::Nonce {
+                authority: nonce.authority.to_string(),
+                nonce: nonce.blockhash().to_string(),
+                lamports_per_signature: nonce.get_lamports_per_signature(),
+            });
+        }
+        return Ok(AccountDetails::Unknown);
+    }
+    if owner == spl_token::ID || owner == spl_token_2022::ID {
+        return Ok(decode_token_account(&owner, &account.data));
+    }
+    if owner == mpl_token_metadata::ID {
+        // Editions and the program's other accounts share the owner, the first byte tells
+        // them apart
+        let metadata = (account.data.first() == Some(&(Key::MetadataV1 as u8)))
+            .then(|| decode_metadata(&account.data).ok())
+            .flatten();
+        let Some(metadata) = metadata else {
+            return Ok(AccountDetails::Unknown);
+        };
+        return Ok(AccountDetails::Metadata {
+            mint: metadata.mint.to_string(),
+            update_authority: metadata.update_authority.to_string(),
+            name: metadata.name,
+            symbol: metadata.symbol,
+            uri: metadata.uri,
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            is_mutable: metadata.is_mutable,
+        });
+    }
+
+    decode_anchor_account(&owner, &account.data)
+}
+
+/// Mints, token accounts and multisigs of the token programs, told apart by length and, for
+/// Token-2022 accounts with extensions, by their account type
+fn decode_token_account(program_id: &Pubkey, data: &[u8]) -> AccountDetails {
+    let program = program_name(program_id).to_string();
+    if let Ok(mint) = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data) {
+        return AccountDetails::Mint {
+            program,
+            mint_authority: coption_to_string(mint.base.mint_authority),
+            freeze_authority: coption_to_string(mint.base.freeze_authority),
+            supply: format_token_amount(mint.base.supply, mint.base.decimals),
+            decimals: mint.base.decimals,
+            extensions: extension_names(mint.get_extension_types().unwrap_or_default()),
+        };
+    }
+    if let Ok(account) = StateWithExtensions::<spl_token_2022::state::Account>::unpack(data) {
+        return AccountDetails::TokenAccount {
+            program,
+            mint: account.base.mint.to_string(),
+            owner: account.base.owner.to_string(),
+            amount: account.base.amount,
+            state: format!("{:?}", account.base.state),
+            delegate: coption_to_string(account.base.delegate),
+            close_authority: coption_to_string(account.base.close_authority),
+            extensions: extension_names(account.get_extension_types().unwrap_or_default()),
+        };
+    }
+    if let Ok(multisig) = spl_token_2022::state::Multisig::unpack(data) {
+        return AccountDetails::Multisig {
+            program,
+            threshold: multisig.m,
+            signers: multisig.signers[..multisig.n as usize]
+                .iter()
+                .map(Pubkey::to_string)
+                .collect(),
+        };
+    }
+    AccountDetails::Unknown
+}
+
+/// `Offer` and `Favorites` accounts of our Anchor programs, recognized by their discriminator
+fn decode_anchor_account(program_id: &Pubkey, data: &[u8]) -> Result<AccountDetails> {
+    if data.len() < ANCHOR_DISCRIMINATOR_SIZE {
+        return Ok(AccountDetails::Unknown);
+    }
+    let (discriminator, mut payload) = data.split_at(ANCHOR_DISCRIMINATOR_SIZE);
+
+    if discriminator == favorites::account_discriminator() {
+        let favorites = favorites::decode_favorites(data)?;
+        return Ok(AccountDetails::Favorites {
+            number: favorites.number,
+            color: favorites.color,
+        });
+    }
+    if discriminator != anchor::discriminator("account", "Offer") {
+        return Ok(AccountDetails::Unknown);
+    }
+
+    // Both escrow programs name their account `Offer`, only the approving one records the
+    // offered amount. Offers of other deployments are told apart by their size
+    let with_offered_amount = match program_id.to_string().as_str() {
+        ESCROW_APPROVE_PROGRAM => true,
+        ESCROW_PROGRAM => false,
+        _ => payload.len() >= APPROVE_ESCROW_OFFER_LEN,
+    };
+    Ok(if with_offered_amount {
+        let offer = ApproveEscrowOffer::deserialize(&mut payload)
+            .context("Failed to decode offer account")?;
+        AccountDetails::Offer {
+            id: offer.id,
+            maker: offer.maker.to_string(),
+            token_mint_a: offer.token_mint_a.to_string(),
+            token_mint_b: offer.token_mint_b.to_string(),
+            token_a_offered_amount: Some(offer.token_a_offered_amount),
+            token_b_wanted_amount: offer.token_b_wanted_amount,
+            bump: offer.bump,
+        }
+    } else {
+        let offer =
+            EscrowOffer::deserialize(&mut payload).context("Failed to decode offer account")?;
+        AccountDetails::Offer {
+            id: offer.id,
+            maker: offer.maker.to_string(),
+            token_mint_a: offer.token_mint_a.to_string(),
+            token_mint_b: offer.token_mint_b.to_string(),
+            token_a_offered_amount: None,
+            token_b_wanted_amount: offer.token_b_wanted_amount,
+            bump: offer.bump,
+        }
+    })
+}
+
+fn program_name(program_id: &Pubkey) -> &'static str {
+    if *program_id == spl_token_2022::ID {
+        "Token-2022"
+    } else {
+        "SPL Token"
+    }
+}
+
+fn coption_to_string(value: COption<Pubkey>) -> Option<String> {
+    Option::<Pubkey>::from(value).map(|key| key.to_string())
+}
+
+fn extension_names(extensions: Vec<ExtensionType>) -> Vec<String> {
+    extensions
+        .iter()
+        .map(|extension| format!("{:?}", extension))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        metadata_account, mint_account, native_metadata_mint_account, token_account,
+    };
+    use borsh::BorshSerialize;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::nonce::state::{Data, DurableNonce, State, Versions};
+
+    fn program_account(owner: &Pubkey, data: Vec<u8>) -> Account {
+        Account {
+            lamports: 1,
+            data,
+            owner: *owner,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn json(account: &Account) -> serde_json::Value {
+        serde_json::to_value(decode_account(account).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn decodes_system_accounts() {
+        let authority = Pubkey::new_unique();
+        let durable_nonce = DurableNonce::from_blockhash(&Hash::new_unique());
+        let nonce = Account::new_data(
+            1_000_000,
+            &Versions::new(State::Initialized(Data::new(
+                authority,
+                durable_nonce,
+                5_000,
+            ))),
+            &solana_sdk::system_program::ID,
+        )
+        .unwrap();
+
+        assert_eq!(
+            json(&Account::new(1, 0, &solana_sdk::system_program::ID))["account_type"],
+            "wallet"
+        );
+        let nonce = json(&nonce);
+        assert_eq!(nonce["account_type"], "nonce");
+        assert_eq!(nonce["authority"], authority.to_string());
+        assert_eq!(nonce["nonce"], durable_nonce.as_hash().to_string());
+        assert_eq!(nonce["lamports_per_signature"], 5_000);
+    }
+
+    #[test]
+    fn decodes_mints_with_extensions() {
+        let authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let spl_mint = json(&mint_account(&spl_token::ID, &authority, 6));
+        assert_eq!(spl_mint["account_type"], "mint");
+        assert_eq!(spl_mint["program"], "SPL Token");
+        assert_eq!(spl_mint["mint_authority"], authority.to_string());
+        assert_eq!(spl_mint["supply"], "0");
+        assert_eq!(spl_mint["decimals"], 6);
+
+        let native = json(&native_metadata_mint_account(&mint, &authority, None));
+        assert_eq!(native["account_type"], "mint");
+        assert_eq!(native["program"], "Token-2022");
+        assert_eq!(native["freeze_authority"], serde_json::Value::Null);
+        assert_eq!(native["extensions"], serde_json::json!(["MetadataPointer"]));
+    }
+
+    #[test]
+    fn decodes_token_accounts_and_multisigs() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let account = json(&token_account(&spl_token_2022::ID, &mint, &owner));
+        assert_eq!(account["account_type"], "token_account");
+        assert_eq!(account["mint"], mint.to_string());
+        assert_eq!(account["owner"], owner.to_string());
+        assert_eq!(account["state"], "Initialized");
+
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut multisig = spl_token::state::Multisig {
+            m: 1,
+            n: 2,
+            is_initialized: true,
+            ..spl_token::state::Multisig::default()
+        };
+        multisig.signers[..2].copy_from_slice(&signers);
+        let mut data = vec![0; spl_token::state::Multisig::LEN];
+        multisig.pack_into_slice(&mut data);
+
+        let multisig = json(&program_account(&spl_token::ID, data));
+        assert_eq!(multisig["account_type"], "multisig");
+        assert_eq!(multisig["threshold"], 1);
+        assert_eq!(
+            multisig["signers"],
+            serde_json::json!([signers[0].to_string(), signers[1].to_string()])
+        );
+    }
+
+    #[test]
+    fn decodes_metaplex_metadata() {
+        let mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+
+        let metadata = json(&metadata_account(
+            &mint,
+            &authority,
+            "My Token",
+            "MTK",
+            "https://x",
+        ));
+
+        assert_eq!(metadata["account_type"], "metadata");
+        assert_eq!(metadata["mint"], mint.to_string());
+        assert_eq!(metadata["name"], "My Token");
+        assert_eq!(metadata["symbol"], "MTK");
+        assert_eq!(metadata["uri"], "https://x");
+    }
+
+    #[test]
+    fn keeps_other_metaplex_accounts_unknown() {
+        let mut edition = vec![Key::MasterEditionV2 as u8];
+        edition.extend([0; 40]);
+        let truncated = vec![Key::MetadataV1 as u8, 1, 2];
+
+        for data in [edition, truncated] {
+            let account = program_account(&mpl_token_metadata::ID, data);
+            assert_eq!(json(&account)["account_type"], "unknown");
+        }
+    }
+
+    #[test]
+    fn decodes_anchor_accounts_by_discriminator() {
+        let maker = Pubkey::new_unique();
+        let mut offer = anchor::discriminator("account", "Offer").to_vec();
+        BorshSerialize::serialize(
+            &(
+                7u64,
+                maker,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                500u64,
+                254u8,
+            ),
+            &mut offer,
+        )
+        .unwrap();
+        let escrow = json(&program_account(&ESCROW_PROGRAM.parse().unwrap(), offer));
+        assert_eq!(escrow["account_type"], "offer");
+        assert_eq!(escrow["id"], 7);
+        assert_eq!(escrow["maker"], maker.to_string());
+        assert_eq!(escrow["token_b_wanted_amount"], 500);
+        assert_eq!(escrow["bump"], 254);
+        assert!(escrow.get("token_a_offered_amount").is_none());
+
+        let mut approve_offer = anchor::discriminator("account", "Offer").to_vec();
+        BorshSerialize::serialize(
+            &(
+                1u64,
+                maker,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                100u64,
+                200u64,
+                255u8,
+            ),
+            &mut approve_offer,
+        )
+        .unwrap();
+        let approve = json(&program_account(&Pubkey::new_unique(), approve_offer));
+        assert_eq!(approve["token_a_offered_amount"], 100);
+        assert_eq!(approve["token_b_wanted_amount"], 200);
+
+        let mut data = favorites::account_discriminator().to_vec();
+        BorshSerialize::serialize(
+            &favorites::Favorites {
+                number: 7,
+                color: "blue".to_string(),
+            },
+            &mut data,
+        )
+        .unwrap();
+        data.resize(data.len() + 20, 0);
+        let favorites = json(&program_account(&favorites::program_id(), data));
+        assert_eq!(favorites["account_type"], "favorites");
+        assert_eq!(favorites["number"], 7);
+        assert_eq!(favorites["color"], "blue");
+
+        let other = json(&program_account(&Pubkey::new_unique(), vec![1; 40]));
+        assert_eq!(other["account_type"], "unknown");
+    }
+}
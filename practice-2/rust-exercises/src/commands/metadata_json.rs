@@ -0,0 +1,116 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::CommandOutput;
+use crate::commands::token_metadata::{parse_key_value, validate_metadata};
+use anyhow::{Context, Result};
+use clap::Args;
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct MetadataJsonArgs {
+    #[clap(long)]
+    pub name: String,
+    #[clap(long)]
+    pub symbol: String,
+    #[clap(long, default_value = "")]
+    pub description: String,
+    /// URL of the token image
+    #[clap(long)]
+    pub image: String,
+    /// Attribute as TRAIT=VALUE, repeat for every attribute
+    #[clap(long = "attribute", value_name = "TRAIT=VALUE", value_parser = parse_attribute)]
+    pub attributes: Vec<Attribute>,
+    #[clap(long, default_value = "metadata.json")]
+    pub outfile: PathBuf,
+}
+
+/// Off-chain metadata following the Metaplex token standard, uploaded and referenced by the
+/// on-chain URI
+#[derive(Serialize)]
+pub struct MetadataJson {
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    pub image: String,
+    pub attributes: Vec<Attribute>,
+}
+
+#[derive(Clone, Serialize, Debug, PartialEq)]
+pub struct Attribute {
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[derive(Serialize)]
+pub struct MetadataJsonOutput {
+    pub file: String,
+    pub metadata: MetadataJson,
+}
+
+impl CommandOutput for MetadataJsonOutput {
+    fn print_text(&self) {
+        println!("💾 - Metadata JSON saved to {}", self.file);
+        println!("📤 - Upload it and pass its URL as --uri");
+    }
+}
+
+fn parse_attribute(value: &str) -> Result<Attribute> {
+    let (trait_type, value) = parse_key_value(value)?;
+    Ok(Attribute { trait_type, value })
+}
+
+pub fn execute(args: MetadataJsonArgs, global: &GlobalArgs) -> Result<()> {
+    global.output.print(&run(args)?)
+}
+
+pub fn run(args: MetadataJsonArgs) -> Result<MetadataJsonOutput> {
+    validate_metadata(&args.name, &args.symbol, "")?;
+
+    let metadata = MetadataJson {
+        name: args.name,
+        symbol: args.symbol,
+        description: args.description,
+        image: args.image,
+        attributes: args.attributes,
+    };
+    std::fs::write(&args.outfile, serde_json::to_string_pretty(&metadata)?)
+        .with_context(|| format!("Failed to write metadata file {}", args.outfile.display()))?;
+
+    Ok(MetadataJsonOutput {
+        file: args.outfile.display().to_string(),
+        metadata,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_attributes() {
+        assert_eq!(
+            parse_attribute("rarity=rare=ish").unwrap(),
+            Attribute {
+                trait_type: "rarity".to_string(),
+                value: "rare=ish".to_string(),
+            }
+        );
+        assert!(parse_attribute("rarity").is_err());
+    }
+
+    #[test]
+    fn rejects_too_long_name_before_writing() {
+        let outfile = std::env::temp_dir().join("rust-exercises-metadata-too-long.json");
+        let args = MetadataJsonArgs {
+            name: "n".repeat(33),
+            symbol: "TKN".to_string(),
+            description: String::new(),
+            image: String::new(),
+            attributes: Vec::new(),
+            outfile: outfile.clone(),
+        };
+
+        assert!(run(args).is_err());
+        assert!(!outfile.exists());
+    }
+}
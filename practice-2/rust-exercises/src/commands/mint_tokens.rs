@@ -1,7 +1,15 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::{fetch_mint, format_token_amount, parse_token_amount};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
 use anyhow::Result;
 use clap::Args;
+use serde::Serialize;
 use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
 use solana_sdk::signer::Signer;
+use std::path::PathBuf;
 
 #[derive(Args)]
 pub struct MintTokensArgs {
@@ -11,44 +19,251 @@ pub struct MintTokensArgs {
     pub token_mint: String,
     #[clap(long)]
     pub recipient: String,
+    /// Amount of tokens, e.g. 12.34, scaled by the mint's decimals
     #[clap(long)]
+    pub amount: String,
+    /// Mint authority, e.g. a multisig account (defaults to the loaded keypair)
+    #[clap(long)]
+    pub mint_authority: Option<String>,
+    /// Keypair file of a multisig signer, repeat for every signer needed to reach the threshold
+    #[clap(long = "signer", value_name = "PATH", requires = "mint_authority")]
+    pub signers: Vec<PathBuf>,
+}
+
+#[derive(Serialize)]
+pub struct MintTokensOutput {
+    pub mint: String,
+    pub recipient: String,
+    /// Amount in base units
     pub amount: u64,
-    #[clap(long, default_value = "2")]
+    pub ui_amount: String,
     pub decimals: u8,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for MintTokensOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Tokens minted");
+        println!("💰 - Amount: {}", self.ui_amount);
+        println!("🔑 - Recipient: {}", self.recipient);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([
+            ExplorerLink::Address(&self.mint),
+            ExplorerLink::Address(&self.recipient),
+        ]);
+        links
+    }
 }
 
-pub fn execute(args: MintTokensArgs) -> Result<()> {
+pub fn execute(args: MintTokensArgs, global: &GlobalArgs) -> Result<()> {
     let client = RpcClient::new(args.cluster.clone());
-    println!("Connected to {}", args.cluster);
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    let sender = crate::commands::load_keypair(global)?;
+    let signers = args
+        .signers
+        .iter()
+        .map(|path| crate::commands::read_keypair_file(path))
+        .collect::<Result<Vec<_>>>()?;
+    let output = run(&client, global, &sender, &signers, args)?;
 
-    let sender = crate::commands::load_keypair()?;
-    let mint_authority = &sender;
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
 
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    multisig_signers: &[Keypair],
+    args: MintTokensArgs,
+) -> Result<MintTokensOutput> {
     let sender_pubkey = sender.pubkey();
-    let mint_authority_pubkey = &sender_pubkey;
     let mint = args.token_mint.parse()?;
     let recipient = args.recipient.parse()?;
+    let mint_info = fetch_mint(client, &mint)?;
+    let amount = parse_token_amount(&args.amount, mint_info.decimals)?;
 
-    let mint_to_instruction = spl_token::instruction::mint_to(
-        &spl_token::ID,
+    let mint_authority_pubkey = match &args.mint_authority {
+        Some(mint_authority) => mint_authority.parse()?,
+        None => sender_pubkey,
+    };
+    if mint_authority_pubkey != sender_pubkey && multisig_signers.is_empty() {
+        anyhow::bail!(
+            "Mint authority {} is not the loaded keypair, pass its signers with --signer",
+            mint_authority_pubkey
+        );
+    }
+    let signer_pubkeys: Vec<Pubkey> = multisig_signers.iter().map(Keypair::pubkey).collect();
+
+    let mint_to_instruction = spl_token_2022::instruction::mint_to_checked(
+        &mint_info.program_id,
         &mint,
         &recipient,
         &mint_authority_pubkey,
-        &[&mint_authority_pubkey],
-        args.amount * 10_u64.pow(args.decimals as u32),
+        &signer_pubkeys.iter().collect::<Vec<_>>(),
+        amount,
+        mint_info.decimals,
     )?;
 
-    let recent_blockhash = client.get_latest_blockhash()?;
-    let trx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+    let mut signers: Vec<&dyn Signer> = vec![sender];
+    signers.extend(multisig_signers.iter().map(|signer| signer as &dyn Signer));
+    let transaction = process_transaction(
+        client,
+        global,
         &[mint_to_instruction],
-        Some(&sender_pubkey),
-        &[&sender, &mint_authority],
-        recent_blockhash,
-    );
+        &sender_pubkey,
+        &signers,
+    )?;
+
+    Ok(MintTokensOutput {
+        mint: mint.to_string(),
+        recipient: args.recipient,
+        amount,
+        ui_amount: format_token_amount(amount, mint_info.decimals),
+        decimals: mint_info.decimals,
+        transaction,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        account_response, assert_instructions, mint_account, MockRpc,
+    };
+    use solana_client::rpc_request::RpcRequest;
+
+    fn rpc_with_mint(mint: &Pubkey, program_id: &Pubkey, decimals: u8) -> MockRpc {
+        let account = mint_account(program_id, &Pubkey::new_unique(), decimals);
+        MockRpc::with_mocks([(RpcRequest::GetAccountInfo, account_response(mint, &account))].into())
+    }
+
+    #[test]
+    fn mints_scaled_amount() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &spl_token::ID, 2);
+        let recipient = Pubkey::new_unique();
+        let args = MintTokensArgs {
+            cluster: String::new(),
+            token_mint: mint.to_string(),
+            recipient: recipient.to_string(),
+            amount: "12.34".to_string(),
+            mint_authority: None,
+            signers: Vec::new(),
+        };
+
+        let output = run(&rpc.client, &GlobalArgs::default(), &sender, &[], args).unwrap();
+
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[spl_token::instruction::mint_to_checked(
+                &spl_token::ID,
+                &mint,
+                &recipient,
+                &sender.pubkey(),
+                &[],
+                1234,
+                2,
+            )
+            .unwrap()],
+        );
+        assert_eq!(output.amount, 1234);
+        assert_eq!(output.ui_amount, "12.34");
+    }
+
+    #[test]
+    fn mints_through_multisig() {
+        let payer = Keypair::new();
+        let signers = [Keypair::new(), Keypair::new()];
+        let multisig = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &spl_token_2022::ID, 0);
+        let recipient = Pubkey::new_unique();
+        let args = MintTokensArgs {
+            cluster: String::new(),
+            token_mint: mint.to_string(),
+            recipient: recipient.to_string(),
+            amount: "10".to_string(),
+            mint_authority: Some(multisig.to_string()),
+            signers: Vec::new(),
+        };
+
+        run(&rpc.client, &GlobalArgs::default(), &payer, &signers, args).unwrap();
+
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &payer.pubkey(),
+            &[spl_token_2022::instruction::mint_to_checked(
+                &spl_token_2022::ID,
+                &mint,
+                &recipient,
+                &multisig,
+                &[&signers[0].pubkey(), &signers[1].pubkey()],
+                10,
+                0,
+            )
+            .unwrap()],
+        );
+    }
+
+    #[test]
+    fn requires_signers_for_foreign_authority() {
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &spl_token::ID, 2);
+        let args = MintTokensArgs {
+            cluster: String::new(),
+            token_mint: mint.to_string(),
+            recipient: Pubkey::new_unique().to_string(),
+            amount: "1".to_string(),
+            mint_authority: Some(Pubkey::new_unique().to_string()),
+            signers: Vec::new(),
+        };
+
+        assert!(run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &Keypair::new(),
+            &[],
+            args
+        )
+        .is_err());
+        assert!(rpc.sent_transactions().is_empty());
+    }
+
+    #[test]
+    fn rejects_more_decimals_than_mint() {
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &spl_token::ID, 2);
+        let args = MintTokensArgs {
+            cluster: String::new(),
+            token_mint: mint.to_string(),
+            recipient: Pubkey::new_unique().to_string(),
+            amount: "1.005".to_string(),
+            mint_authority: None,
+            signers: Vec::new(),
+        };
 
-    let signature = client.send_and_confirm_transaction_with_spinner(&trx)?;
-    println!("✅ - Tokens minted, transaction signature: {}", signature);
-    println!("🔑 - Recipient: {}", args.recipient);
+        let Err(error) = run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &Keypair::new(),
+            &[],
+            args,
+        ) else {
+            panic!("expected an error");
+        };
 
-    Ok(())
+        assert!(error.to_string().contains("more decimal places"));
+        assert!(rpc.sent_transactions().is_empty());
+    }
 }
@@ -1,25 +1,92 @@
+use crate::cli::GlobalArgs;
 use anyhow::Context;
 use anyhow::Result;
-use dotenvy_macro::dotenv;
 use solana_sdk::signature::Keypair;
 use solana_sdk::signer::Signer;
+use std::path::{Path, PathBuf};
 
+pub mod anchor;
+pub mod broadcast;
+pub mod burn;
+pub mod close_token_account;
 pub mod create_token_account;
 pub mod create_token_metadata;
 pub mod create_token_mint;
+pub mod distribute;
+pub mod favorites;
+pub mod freeze_account;
+pub mod inspect;
+pub mod metadata_json;
 pub mod mint_tokens;
+pub mod multisig;
+pub mod nonce;
+pub mod output;
+pub mod program_error;
 pub mod send_sol;
+pub mod set_authority;
+pub mod show_token_metadata;
+#[cfg(test)]
+mod test_utils;
+pub mod token;
+pub mod token_metadata;
+pub mod transaction;
+pub mod transfer_tokens;
+pub mod tx;
+pub mod update_token_metadata;
 
-fn load_keypair() -> Result<Keypair> {
-    let secret_key_str = dotenv!("SECRET_KEY");
-    let secret_key: Vec<u8> = serde_json::from_str(&secret_key_str)
-        .context("Failed to parse SECRET_KEY from environment variable")?;
+/// Resolves the keypair from `--keypair`/`SOLANA_KEYPAIR`, then the `SECRET_KEY` variable
+/// (also read from .env at runtime) and finally the Solana CLI default keypair
+fn read_keypair(global: &GlobalArgs) -> Result<Keypair> {
+    if let Some(path) = &global.keypair {
+        return read_keypair_file(path);
+    }
 
-    let keypair =
-        Keypair::from_bytes(&secret_key).context("Failed to load keypair from secret key")?;
+    if let Ok(secret_key_str) = std::env::var("SECRET_KEY") {
+        return parse_secret_key(&secret_key_str)
+            .context("Failed to parse SECRET_KEY from environment variable");
+    }
 
-    println!("Successfully loaded keypair.");
-    println!("Public key: {}", keypair.pubkey());
+    match default_keypair_path() {
+        Some(path) if path.exists() => read_keypair_file(&path),
+        _ => anyhow::bail!(
+            "No keypair found, pass --keypair, set SOLANA_KEYPAIR or SECRET_KEY, or create ~/.config/solana/id.json"
+        ),
+    }
+}
+
+fn load_keypair(global: &GlobalArgs) -> Result<Keypair> {
+    let keypair = read_keypair(global)?;
+
+    global.output.status("Successfully loaded keypair.");
+    global
+        .output
+        .status(format!("Public key: {}", keypair.pubkey()));
 
     Ok(keypair)
 }
+
+fn default_keypair_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")?;
+    Some(Path::new(&home).join(".config/solana/id.json"))
+}
+
+fn read_keypair_file(path: &Path) -> Result<Keypair> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read keypair file {}", path.display()))?;
+    parse_secret_key(&content)
+        .with_context(|| format!("Failed to load keypair from {}", path.display()))
+}
+
+/// Accepts a JSON byte array (Solana CLI) or a base58 string (Phantom export)
+fn parse_secret_key(secret_key_str: &str) -> Result<Keypair> {
+    let secret_key_str = secret_key_str.trim();
+    let secret_key: Vec<u8> = if secret_key_str.starts_with('[') {
+        serde_json::from_str(secret_key_str).context("Invalid JSON secret key")?
+    } else {
+        solana_sdk::bs58::decode(secret_key_str)
+            .into_vec()
+            .context("Invalid base58 secret key")?
+    };
+
+    Keypair::from_bytes(&secret_key).context("Failed to load keypair from secret key")
+}
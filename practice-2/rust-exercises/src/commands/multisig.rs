@@ -0,0 +1,263 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::TokenProgramArgs;
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use spl_token_2022::instruction::MAX_SIGNERS;
+use spl_token_2022::solana_program::program_pack::Pack;
+use spl_token_2022::state::Multisig;
+
+#[derive(Args)]
+pub struct MultisigArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    #[clap(subcommand)]
+    pub command: MultisigCommand,
+}
+
+#[derive(Subcommand)]
+pub enum MultisigCommand {
+    /// Create an M-of-N SPL token multisig account
+    Create {
+        /// Comma separated signer public keys
+        #[clap(long, value_delimiter = ',', required = true)]
+        signers: Vec<String>,
+        /// Number of signatures required
+        #[clap(long)]
+        threshold: u8,
+        #[clap(flatten)]
+        token_program: TokenProgramArgs,
+    },
+}
+
+#[derive(Serialize)]
+pub struct CreateMultisigOutput {
+    pub multisig: String,
+    pub signers: Vec<String>,
+    pub threshold: u8,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for CreateMultisigOutput {
+    fn print_text(&self) {
+        self.transaction.print_text(&format!(
+            "Multisig ({}/{}) created",
+            self.threshold,
+            self.signers.len()
+        ));
+        println!("🔑 - Multisig address: {}", self.multisig);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([ExplorerLink::Address(&self.multisig)]);
+        links.extend(
+            self.signers
+                .iter()
+                .map(|signer| ExplorerLink::Address(signer)),
+        );
+        links
+    }
+}
+
+pub fn execute(args: MultisigArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    match args.command {
+        MultisigCommand::Create {
+            signers,
+            threshold,
+            token_program,
+        } => {
+            let sender = crate::commands::load_keypair(global)?;
+            let signers = signers
+                .iter()
+                .map(|signer| signer.trim().parse())
+                .collect::<Result<Vec<Pubkey>, _>>()?;
+            let output = create(
+                &client,
+                global,
+                &sender,
+                &signers,
+                threshold,
+                &token_program.program_id(),
+            )?;
+            global
+                .output
+                .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+        }
+    }
+}
+
+pub fn create(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    signers: &[Pubkey],
+    threshold: u8,
+    program_id: &Pubkey,
+) -> Result<CreateMultisigOutput> {
+    if signers.len() > MAX_SIGNERS {
+        bail!("A multisig supports at most {} signers", MAX_SIGNERS);
+    }
+    if threshold == 0 || threshold as usize > signers.len() {
+        bail!(
+            "Threshold must be between 1 and the number of signers ({})",
+            signers.len()
+        );
+    }
+
+    let multisig_account = Keypair::new();
+    let sender_pubkey = sender.pubkey();
+    let multisig_pubkey = multisig_account.pubkey();
+
+    let minimum_balance_for_rent_exemption =
+        client.get_minimum_balance_for_rent_exemption(Multisig::LEN)?;
+
+    let create_account_instruction = solana_sdk::system_instruction::create_account(
+        &sender_pubkey,
+        &multisig_pubkey,
+        minimum_balance_for_rent_exemption,
+        Multisig::LEN as u64,
+        program_id,
+    );
+
+    let initialize_multisig_instruction = spl_token_2022::instruction::initialize_multisig(
+        program_id,
+        &multisig_pubkey,
+        &signers.iter().collect::<Vec<_>>(),
+        threshold,
+    )?;
+
+    let transaction = process_transaction(
+        client,
+        global,
+        &[create_account_instruction, initialize_multisig_instruction],
+        &sender_pubkey,
+        &[sender, &multisig_account],
+    )?;
+
+    Ok(CreateMultisigOutput {
+        multisig: multisig_pubkey.to_string(),
+        signers: signers.iter().map(Pubkey::to_string).collect(),
+        threshold,
+        transaction,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{assert_instructions, MockRpc};
+
+    #[test]
+    fn creates_multisig() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let signers = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+
+        let output = create(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            &signers,
+            2,
+            &spl_token::ID,
+        )
+        .unwrap();
+
+        let multisig = output.multisig.parse().unwrap();
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[
+                solana_sdk::system_instruction::create_account(
+                    &sender.pubkey(),
+                    &multisig,
+                    20,
+                    Multisig::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_multisig(
+                    &spl_token::ID,
+                    &multisig,
+                    &[&signers[0], &signers[1], &signers[2]],
+                    2,
+                )
+                .unwrap(),
+            ],
+        );
+    }
+
+    #[test]
+    fn creates_token_2022_multisig() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let output = create(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            &signers,
+            1,
+            &spl_token_2022::ID,
+        )
+        .unwrap();
+
+        let multisig = output.multisig.parse().unwrap();
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[
+                solana_sdk::system_instruction::create_account(
+                    &sender.pubkey(),
+                    &multisig,
+                    20,
+                    Multisig::LEN as u64,
+                    &spl_token_2022::ID,
+                ),
+                spl_token_2022::instruction::initialize_multisig(
+                    &spl_token_2022::ID,
+                    &multisig,
+                    &[&signers[0], &signers[1]],
+                    1,
+                )
+                .unwrap(),
+            ],
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_threshold() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        for threshold in [0, 3] {
+            assert!(create(
+                &rpc.client,
+                &GlobalArgs::default(),
+                &sender,
+                &signers,
+                threshold,
+                &spl_token::ID
+            )
+            .is_err());
+        }
+        assert!(rpc.sent_transactions().is_empty());
+    }
+}
@@ -0,0 +1,328 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::{parse_token_amount, SOL_DECIMALS};
+use crate::commands::transaction::{
+    fetch_nonce, load_nonce_authority, process_transaction, TransactionOutcome,
+};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::nonce::State;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+
+#[derive(Args)]
+pub struct NonceArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    #[clap(subcommand)]
+    pub command: NonceCommand,
+}
+
+#[derive(Subcommand)]
+pub enum NonceCommand {
+    /// Create a new nonce account funded with the rent exempt minimum
+    Create {
+        /// Nonce authority (defaults to the loaded keypair)
+        #[clap(long)]
+        authority: Option<String>,
+    },
+    /// Show the authority and current nonce of a nonce account
+    Show {
+        #[clap(long)]
+        address: String,
+    },
+    /// Advance the nonce, invalidating transactions signed with the current one
+    Advance {
+        #[clap(long)]
+        address: String,
+    },
+    /// Withdraw SOL from a nonce account
+    Withdraw {
+        #[clap(long)]
+        address: String,
+        #[clap(long)]
+        recipient: String,
+        /// SOL to withdraw, e.g. 0.1
+        #[clap(long)]
+        sols: String,
+    },
+}
+
+#[derive(Serialize)]
+pub struct CreateNonceOutput {
+    pub nonce_account: String,
+    pub authority: String,
+    pub lamports: u64,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for CreateNonceOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Nonce account created");
+        println!("🔑 - Nonce account: {}", self.nonce_account);
+        println!("👤 - Nonce authority: {}", self.authority);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([
+            ExplorerLink::Address(&self.nonce_account),
+            ExplorerLink::Address(&self.authority),
+        ]);
+        links
+    }
+}
+
+#[derive(Serialize)]
+pub struct NonceInfo {
+    pub address: String,
+    pub authority: String,
+    pub nonce: String,
+    pub lamports_per_signature: u64,
+}
+
+impl CommandOutput for NonceInfo {
+    fn print_text(&self) {
+        println!("🔑 - Nonce account: {}", self.address);
+        println!("👤 - Nonce authority: {}", self.authority);
+        println!("🔢 - Nonce: {}", self.nonce);
+        println!(
+            "💸 - Lamports per signature: {}",
+            self.lamports_per_signature
+        );
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        vec![
+            ExplorerLink::Address(&self.address),
+            ExplorerLink::Address(&self.authority),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub struct NonceTransactionOutput {
+    pub nonce_account: String,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for NonceTransactionOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Nonce account updated");
+        println!("🔑 - Nonce account: {}", self.nonce_account);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([ExplorerLink::Address(&self.nonce_account)]);
+        links
+    }
+}
+
+pub fn execute(args: NonceArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    match args.command {
+        NonceCommand::Create { authority } => {
+            let sender = crate::commands::load_keypair(global)?;
+            let authority = match authority {
+                Some(authority) => authority.parse()?,
+                None => sender.pubkey(),
+            };
+            global.output.print_with_links(
+                &ExplorerLinks::new(global.explorer, &client.url()),
+                &create(&client, global, &sender, &authority)?,
+            )
+        }
+        NonceCommand::Show { address } => global.output.print_with_links(
+            &ExplorerLinks::new(global.explorer, &client.url()),
+            &show(&client, &address.parse()?)?,
+        ),
+        NonceCommand::Advance { address } => {
+            let sender = crate::commands::load_keypair(global)?;
+            global.output.print_with_links(
+                &ExplorerLinks::new(global.explorer, &client.url()),
+                &advance(&client, global, &sender, &address.parse()?)?,
+            )
+        }
+        NonceCommand::Withdraw {
+            address,
+            recipient,
+            sols,
+        } => {
+            let sender = crate::commands::load_keypair(global)?;
+            let output = withdraw(
+                &client,
+                global,
+                &sender,
+                &address.parse()?,
+                &recipient.parse()?,
+                parse_token_amount(&sols, SOL_DECIMALS)?,
+            )?;
+            global
+                .output
+                .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+        }
+    }
+}
+
+pub fn create(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    authority: &Pubkey,
+) -> Result<CreateNonceOutput> {
+    let nonce_account = Keypair::new();
+    let lamports = client.get_minimum_balance_for_rent_exemption(State::size())?;
+
+    let instructions = system_instruction::create_nonce_account(
+        &sender.pubkey(),
+        &nonce_account.pubkey(),
+        authority,
+        lamports,
+    );
+    let transaction = process_transaction(
+        client,
+        global,
+        &instructions,
+        &sender.pubkey(),
+        &[sender, &nonce_account],
+    )?;
+
+    Ok(CreateNonceOutput {
+        nonce_account: nonce_account.pubkey().to_string(),
+        authority: authority.to_string(),
+        lamports,
+        transaction,
+    })
+}
+
+pub fn show(client: &RpcClient, address: &Pubkey) -> Result<NonceInfo> {
+    let nonce = fetch_nonce(client, address)?;
+
+    Ok(NonceInfo {
+        address: address.to_string(),
+        authority: nonce.authority.to_string(),
+        nonce: nonce.blockhash().to_string(),
+        lamports_per_signature: nonce.get_lamports_per_signature(),
+    })
+}
+
+pub fn advance(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    address: &Pubkey,
+) -> Result<NonceTransactionOutput> {
+    let authority = load_nonce_authority(global)?;
+    let authority: &Keypair = authority.as_ref().unwrap_or(sender);
+
+    let instruction = system_instruction::advance_nonce_account(address, &authority.pubkey());
+    let transaction = process_transaction(
+        client,
+        global,
+        &[instruction],
+        &sender.pubkey(),
+        &[sender, authority],
+    )?;
+
+    Ok(NonceTransactionOutput {
+        nonce_account: address.to_string(),
+        transaction,
+    })
+}
+
+pub fn withdraw(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    address: &Pubkey,
+    recipient: &Pubkey,
+    lamports: u64,
+) -> Result<NonceTransactionOutput> {
+    let authority = load_nonce_authority(global)?;
+    let authority: &Keypair = authority.as_ref().unwrap_or(sender);
+
+    let instruction = system_instruction::withdraw_nonce_account(
+        address,
+        &authority.pubkey(),
+        recipient,
+        lamports,
+    );
+    let transaction = process_transaction(
+        client,
+        global,
+        &[instruction],
+        &sender.pubkey(),
+        &[sender, authority],
+    )?;
+
+    Ok(NonceTransactionOutput {
+        nonce_account: address.to_string(),
+        transaction,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{assert_instructions, MockRpc};
+
+    #[test]
+    fn creates_nonce_account() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let authority = Pubkey::new_unique();
+
+        let output = create(&rpc.client, &GlobalArgs::default(), &sender, &authority).unwrap();
+
+        // The mock RPC reports 20 lamports as the rent exempt minimum
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &system_instruction::create_nonce_account(
+                &sender.pubkey(),
+                &output.nonce_account.parse().unwrap(),
+                &authority,
+                20,
+            ),
+        );
+    }
+
+    #[test]
+    fn withdraws_with_payer_as_authority() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let nonce = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+
+        withdraw(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            &nonce,
+            &recipient,
+            500,
+        )
+        .unwrap();
+
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[system_instruction::withdraw_nonce_account(
+                &nonce,
+                &sender.pubkey(),
+                &recipient,
+                500,
+            )],
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Display;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Result of a command, rendered as text or serialized as JSON
+pub trait CommandOutput: Serialize {
+    fn print_text(&self);
+
+    /// Signatures and addresses of the output to link to the block explorer
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        Vec::new()
+    }
+}
+
+/// Transaction signature or account address an output links to
+pub enum ExplorerLink<'a> {
+    Tx(&'a str),
+    Address(&'a str),
+}
+
+impl OutputFormat {
+    pub fn print<T: CommandOutput>(&self, output: &T) -> Result<()> {
+        match self {
+            OutputFormat::Text => output.print_text(),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(output)?),
+        }
+        Ok(())
+    }
+
+    /// Prints the output followed by explorer links to the signatures and addresses it
+    /// declares, added as an `explorer_links` object in JSON
+    pub fn print_with_links<T: CommandOutput>(
+        &self,
+        links: &ExplorerLinks,
+        output: &T,
+    ) -> Result<()> {
+        let found = links.resolve(&output.explorer_links());
+        match self {
+            OutputFormat::Text => {
+                output.print_text();
+                if !found.is_empty() {
+                    println!("🔗 - Explorer links:");
+                }
+                for (id, url) in found {
+                    println!("    {}: {}", id, url);
+                }
+            }
+            OutputFormat::Json => {
+                let mut value = serde_json::to_value(output)?;
+                if let Value::Object(fields) = &mut value {
+                    let found = found
+                        .into_iter()
+                        .map(|(id, url)| (id, Value::String(url)))
+                        .collect();
+                    fields.insert("explorer_links".to_string(), Value::Object(found));
+                }
+                println!("{}", serde_json::to_string_pretty(&value)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Progress messages go to stderr in JSON mode so stdout stays parseable
+    pub fn status(&self, message: impl Display) {
+        match self {
+            OutputFormat::Text => println!("{}", message),
+            OutputFormat::Json => eprintln!("{}", message),
+        }
+    }
+}
+
+/// Block explorer selected with `--explorer`
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Explorer {
+    #[default]
+    Solana,
+    Solscan,
+    Solanafm,
+}
+
+/// Builds explorer links to transactions and accounts of the cluster a command talks to
+pub struct ExplorerLinks {
+    explorer: Explorer,
+    cluster: String,
+}
+
+impl ExplorerLinks {
+    pub fn new(explorer: Explorer, cluster: &str) -> Self {
+        ExplorerLinks {
+            explorer,
+            cluster: cluster.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn tx(&self, signature: &impl Display) -> String {
+        self.url("tx", signature)
+    }
+
+    pub fn address(&self, address: &impl Display) -> String {
+        let path = match self.explorer {
+            Explorer::Solana | Explorer::Solanafm => "address",
+            Explorer::Solscan => "account",
+        };
+        self.url(path, address)
+    }
+
+    fn url(&self, path: &str, id: &impl Display) -> String {
+        let base = match self.explorer {
+            Explorer::Solana => "https://explorer.solana.com",
+            Explorer::Solscan => "https://solscan.io",
+            Explorer::Solanafm => "https://solana.fm",
+        };
+        format!("{}/{}/{}{}", base, path, id, self.cluster_query())
+    }
+
+    /// Query selecting the cluster, empty for mainnet which every explorer defaults to.
+    /// Other RPC endpoints are passed on as `customUrl`
+    fn cluster_query(&self) -> String {
+        let cluster = match self.cluster.as_str() {
+            "https://api.mainnet-beta.solana.com" => return String::new(),
+            "https://api.devnet.solana.com" => "devnet",
+            "https://api.testnet.solana.com" => "testnet",
+            url => {
+                let cluster = match self.explorer {
+                    Explorer::Solana | Explorer::Solscan => "custom",
+                    Explorer::Solanafm => "localnet-solana",
+                };
+                return format!("?cluster={}&customUrl={}", cluster, encode_query_value(url));
+            }
+        };
+        match self.explorer {
+            Explorer::Solana | Explorer::Solscan => format!("?cluster={}", cluster),
+            Explorer::Solanafm => format!("?cluster={}-solana", cluster),
+        }
+    }
+
+    /// URL of every link, keyed by the signature or address and without repetitions
+    fn resolve(&self, links: &[ExplorerLink]) -> Vec<(String, String)> {
+        let mut resolved: Vec<(String, String)> = Vec::new();
+        for link in links {
+            let (id, url) = match link {
+                ExplorerLink::Tx(signature) => (signature, self.tx(signature)),
+                ExplorerLink::Address(address) => (address, self.address(address)),
+            };
+            if !resolved.iter().any(|(resolved_id, _)| resolved_id == id) {
+                resolved.push((id.to_string(), url));
+            }
+        }
+        resolved
+    }
+}
+
+/// Percent-encodes everything but unreserved characters, for use in a query string
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn selects_cluster_per_explorer() {
+        let address = Pubkey::new_unique();
+        let devnet = "https://api.devnet.solana.com/";
+
+        assert_eq!(
+            ExplorerLinks::new(Explorer::Solana, devnet).address(&address),
+            format!(
+                "https://explorer.solana.com/address/{}?cluster=devnet",
+                address
+            )
+        );
+        assert_eq!(
+            ExplorerLinks::new(Explorer::Solscan, devnet).address(&address),
+            format!("https://solscan.io/account/{}?cluster=devnet", address)
+        );
+        assert_eq!(
+            ExplorerLinks::new(Explorer::Solanafm, devnet).tx(&"sig"),
+            "https://solana.fm/tx/sig?cluster=devnet-solana"
+        );
+        assert_eq!(
+            ExplorerLinks::new(Explorer::Solana, "https://api.mainnet-beta.solana.com").tx(&"sig"),
+            "https://explorer.solana.com/tx/sig"
+        );
+    }
+
+    #[test]
+    fn passes_custom_rpc_url() {
+        let links = ExplorerLinks::new(Explorer::Solana, "http://localhost:8899");
+
+        assert_eq!(
+            links.tx(&"sig"),
+            "https://explorer.solana.com/tx/sig?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+        );
+    }
+
+    #[test]
+    fn resolves_declared_links_once() {
+        let links = ExplorerLinks::new(Explorer::Solscan, "https://api.devnet.solana.com");
+
+        let resolved = links.resolve(&[
+            ExplorerLink::Tx("sig"),
+            ExplorerLink::Address("mint"),
+            ExplorerLink::Address("mint"),
+        ]);
+
+        assert_eq!(
+            resolved,
+            [
+                (
+                    "sig".to_string(),
+                    "https://solscan.io/tx/sig?cluster=devnet".to_string()
+                ),
+                (
+                    "mint".to_string(),
+                    "https://solscan.io/account/mint?cluster=devnet".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use crate::commands::anchor::{ESCROW_APPROVE_PROGRAM, ESCROW_PROGRAM, FAVORITES_PROGRAM};
+use solana_sdk::decode_error::DecodeError;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction::SystemError;
+use solana_sdk::transaction::TransactionError;
+use spl_token::error::TokenError;
+use spl_token_2022::error::TokenError as Token2022Error;
+
+/// Anchor programs number their own `#[error_code]` errors from this code on
+const ANCHOR_USER_ERROR_OFFSET: u32 = 6000;
+
+/// Code, name and message of a program error
+type ProgramError = (u32, &'static str, &'static str);
+
+/// `#[error_code]` errors of the known Anchor programs, codes are the offset plus the variant
+const PROGRAM_ERRORS: &[(&str, &[ProgramError])] = &[
+    (FAVORITES_PROGRAM, &[]),
+    (
+        ESCROW_PROGRAM,
+        &[(6000, "CustomError", "Custom error message")],
+    ),
+    (
+        ESCROW_APPROVE_PROGRAM,
+        &[
+            (6000, "CustomError", "Custom error message"),
+            (15000, "InsufficientFunds", "Insufficient funds"),
+            (
+                16000,
+                "MakerTokenAccountAmountLessThanOffered",
+                "Maker token account amount is less than offered amount",
+            ),
+        ],
+    ),
+];
+
+/// Errors raised by the Anchor framework itself, shared by every Anchor program
+const ANCHOR_ERRORS: &[(u32, &str)] = &[
+    (100, "InstructionMissing"),
+    (101, "InstructionFallbackNotFound"),
+    (102, "InstructionDidNotDeserialize"),
+    (103, "InstructionDidNotSerialize"),
+    (2000, "ConstraintMut"),
+    (2001, "ConstraintHasOne"),
+    (2002, "ConstraintSigner"),
+    (2003, "ConstraintRaw"),
+    (2004, "ConstraintOwner"),
+    (2005, "ConstraintRentExempt"),
+    (2006, "ConstraintSeeds"),
+    (2007, "ConstraintExecutable"),
+    (2008, "ConstraintState"),
+    (2009, "ConstraintAssociated"),
+    (2010, "ConstraintAssociatedInit"),
+    (2011, "ConstraintClose"),
+    (2012, "ConstraintAddress"),
+    (2013, "ConstraintZero"),
+    (2014, "ConstraintTokenMint"),
+    (2015, "ConstraintTokenOwner"),
+    (2016, "ConstraintMintMintAuthority"),
+    (2017, "ConstraintMintFreezeAuthority"),
+    (2018, "ConstraintMintDecimals"),
+    (2019, "ConstraintSpace"),
+    (3000, "AccountDiscriminatorAlreadySet"),
+    (3001, "AccountDiscriminatorNotFound"),
+    (3002, "AccountDiscriminatorMismatch"),
+    (3003, "AccountDidNotDeserialize"),
+    (3004, "AccountDidNotSerialize"),
+    (3005, "AccountNotEnoughKeys"),
+    (3006, "AccountNotMutable"),
+    (3007, "AccountOwnedByWrongProgram"),
+    (3008, "InvalidProgramId"),
+    (3009, "InvalidProgramExecutable"),
+    (3010, "AccountNotSigner"),
+    (3011, "AccountNotSystemOwned"),
+    (3012, "AccountNotInitialized"),
+    (3013, "AccountNotProgramData"),
+    (3014, "AccountNotAssociatedTokenAccount"),
+    (3015, "AccountSysvarMismatch"),
+    (3016, "AccountReallocExceedsLimit"),
+    (3017, "AccountDuplicateReallocs"),
+    (4100, "DeclaredProgramIdMismatch"),
+    (5000, "Deprecated"),
+];
+
+/// Describes a failed transaction, naming the program that failed and, for custom program
+/// errors, the SPL token, system program or known Anchor program error behind the code
+pub fn describe_transaction_error(error: &TransactionError, message: &Message) -> String {
+    let TransactionError::InstructionError(index, instruction_error) = error else {
+        return error.to_string();
+    };
+    let program_id = message
+        .instructions
+        .get(*index as usize)
+        .and_then(|instruction| {
+            message
+                .account_keys
+                .get(instruction.program_id_index as usize)
+        });
+
+    let description = match (instruction_error, program_id) {
+        (InstructionError::Custom(code), Some(program_id)) => {
+            custom_error_name(program_id, *code).unwrap_or_else(|| instruction_error.to_string())
+        }
+        _ => instruction_error.to_string(),
+    };
+    match program_id {
+        Some(program_id) => format!(
+            "instruction {} ({}) failed: {}",
+            index, program_id, description
+        ),
+        None => format!("instruction {} failed: {}", index, description),
+    }
+}
+
+/// Name of custom error `code` raised by `program_id`, `None` when it is not a known error
+pub fn custom_error_name(program_id: &Pubkey, code: u32) -> Option<String> {
+    if *program_id == spl_token::ID {
+        let error: TokenError = TokenError::decode_custom_error_to_enum(code)?;
+        return Some(format!("{:?} ({}, code {})", error, error, code));
+    }
+    if *program_id == spl_token_2022::ID {
+        let error: Token2022Error = Token2022Error::decode_custom_error_to_enum(code)?;
+        return Some(format!("{:?} ({}, code {})", error, error, code));
+    }
+    if *program_id == solana_sdk::system_program::ID {
+        let error: SystemError = SystemError::decode_custom_error_to_enum(code)?;
+        return Some(format!("{:?} ({}, code {})", error, error, code));
+    }
+
+    // Other programs number their errors freely, Anchor codes would only mislabel them
+    let (_, program_errors) = PROGRAM_ERRORS
+        .iter()
+        .find(|(anchor_program, _)| program_id.to_string() == *anchor_program)?;
+    if let Some((_, name, message)) = program_errors
+        .iter()
+        .find(|(error_code, _, _)| *error_code == code)
+    {
+        return Some(format!("{} ({}, code {})", name, message, code));
+    }
+    if let Some((_, name)) = ANCHOR_ERRORS
+        .iter()
+        .find(|(anchor_code, _)| *anchor_code == code)
+    {
+        return Some(format!("Anchor error {} (code {})", name, code));
+    }
+    (code >= ANCHOR_USER_ERROR_OFFSET).then(|| {
+        format!(
+            "program error {} (code {}, Anchor #[error_code] variant {})",
+            code,
+            code,
+            code - ANCHOR_USER_ERROR_OFFSET
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn message_with_program(program_id: &Pubkey) -> Message {
+        let instruction =
+            solana_sdk::instruction::Instruction::new_with_bytes(*program_id, &[], Vec::new());
+        Message::new(&[instruction], Some(&Pubkey::new_unique()))
+    }
+
+    #[test]
+    fn names_token_errors() {
+        let error = TransactionError::InstructionError(0, InstructionError::Custom(1));
+
+        let description = describe_transaction_error(&error, &message_with_program(&spl_token::ID));
+
+        assert_eq!(
+            description,
+            format!(
+                "instruction 0 ({}) failed: InsufficientFunds (Insufficient funds, code 1)",
+                spl_token::ID
+            )
+        );
+    }
+
+    #[test]
+    fn names_anchor_errors() {
+        let program_id = Pubkey::from_str(ESCROW_APPROVE_PROGRAM).unwrap();
+
+        assert_eq!(
+            custom_error_name(&program_id, 3012).unwrap(),
+            "Anchor error AccountNotInitialized (code 3012)"
+        );
+        assert_eq!(
+            custom_error_name(&program_id, 15000).unwrap(),
+            "InsufficientFunds (Insufficient funds, code 15000)"
+        );
+        assert!(custom_error_name(&program_id, 6001)
+            .unwrap()
+            .contains("variant 1"));
+        assert_eq!(custom_error_name(&program_id, 42), None);
+    }
+
+    #[test]
+    fn keeps_anchor_codes_of_other_programs() {
+        let metadata_program = mpl_token_metadata::ID;
+
+        assert_eq!(custom_error_name(&metadata_program, 3012), None);
+        assert_eq!(custom_error_name(&metadata_program, 6001), None);
+        assert_eq!(
+            custom_error_name(&spl_associated_token_account::ID, 2),
+            None
+        );
+    }
+
+    #[test]
+    fn keeps_other_errors() {
+        let error = TransactionError::InstructionError(0, InstructionError::Custom(42));
+        let program_id = Pubkey::new_unique();
+
+        assert!(
+            describe_transaction_error(&error, &message_with_program(&program_id))
+                .ends_with("custom program error: 0x2a")
+        );
+        assert_eq!(
+            describe_transaction_error(
+                &TransactionError::BlockhashNotFound,
+                &message_with_program(&program_id)
+            ),
+            TransactionError::BlockhashNotFound.to_string()
+        );
+    }
+}
@@ -1,14 +1,17 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::{parse_token_amount, SOL_DECIMALS};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
 use anyhow::Result;
 use clap::Args;
+use serde::Serialize;
 use solana_client::rpc_client::RpcClient;
 use solana_program::instruction::Instruction;
-use solana_program::native_token::sol_to_lamports;
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::signature::Signer;
-use solana_sdk::transaction::Transaction;
+use solana_sdk::signature::{Keypair, Signer};
 use std::str::FromStr;
 
-const MEMO_PROGRAM: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+pub const MEMO_PROGRAM: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
 
 #[derive(Args)]
 pub struct SendSolArgs {
@@ -16,21 +19,74 @@ pub struct SendSolArgs {
     pub cluster: String,
     #[clap(long)]
     pub recipient: String,
+    /// SOL to send, e.g. 0.1
     #[clap(long)]
-    pub sols: f64,
+    pub sols: String,
     #[clap(long)]
     pub memo: Option<String>,
 }
 
-pub fn execute(args: SendSolArgs) -> Result<()> {
+#[derive(Serialize)]
+pub struct SendSolOutput {
+    pub sender: String,
+    pub recipient: String,
+    pub lamports: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for SendSolOutput {
+    fn print_text(&self) {
+        if let Some(memo) = &self.memo {
+            println!("📝 - Memo added to transaction: {}", memo);
+        }
+        self.transaction.print_text("SOL sent");
+        println!("🔑 - Recipient: {}", self.recipient);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([
+            ExplorerLink::Address(&self.sender),
+            ExplorerLink::Address(&self.recipient),
+        ]);
+        links
+    }
+}
+
+pub fn memo_instruction(memo: &str) -> Result<Instruction> {
+    Ok(Instruction::new_with_bytes(
+        Pubkey::from_str(MEMO_PROGRAM)?,
+        memo.as_bytes(),
+        vec![],
+    ))
+}
+
+pub fn execute(args: SendSolArgs, global: &GlobalArgs) -> Result<()> {
     let client = RpcClient::new(args.cluster.clone());
-    println!("Connected to {}", args.cluster);
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
 
-    let sender = crate::commands::load_keypair()?;
+    let sender = crate::commands::load_keypair(global)?;
+    let output = run(&client, global, &sender, args)?;
 
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    args: SendSolArgs,
+) -> Result<SendSolOutput> {
     let sender_pubkey = sender.pubkey();
     let recipient = args.recipient.parse()?;
-    let lamports = sol_to_lamports(args.sols);
+    let lamports = parse_token_amount(&args.sols, SOL_DECIMALS)?;
 
     let mut instructions = vec![solana_sdk::system_instruction::transfer(
         &sender_pubkey,
@@ -38,27 +94,101 @@ pub fn execute(args: SendSolArgs) -> Result<()> {
         lamports,
     )];
 
-    if let Some(memo) = args.memo {
-        println!("📝 - Adding memo to transaction: {}", memo);
-        instructions.push(Instruction::new_with_bytes(
-            Pubkey::from_str(MEMO_PROGRAM)?,
-            memo.as_bytes(),
-            vec![],
-        ));
+    if let Some(memo) = &args.memo {
+        instructions.push(memo_instruction(memo)?);
     }
 
-    let recent_blockhash = client.get_latest_blockhash()?;
-    let trx = Transaction::new_signed_with_payer(
-        &instructions,
-        Some(&sender_pubkey),
-        &[&sender],
-        recent_blockhash,
-    );
+    let transaction =
+        process_transaction(client, global, &instructions, &sender_pubkey, &[sender])?;
 
-    let signature = client.send_and_confirm_transaction_with_spinner(&trx)?;
+    Ok(SendSolOutput {
+        sender: sender_pubkey.to_string(),
+        recipient: recipient.to_string(),
+        lamports,
+        memo: args.memo,
+        transaction,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{assert_instructions, MockRpc};
+
+    fn args(memo: Option<&str>) -> SendSolArgs {
+        SendSolArgs {
+            cluster: String::new(),
+            recipient: Pubkey::new_unique().to_string(),
+            sols: "1.5".to_string(),
+            memo: memo.map(String::from),
+        }
+    }
+
+    #[test]
+    fn rejects_inexact_and_negative_amounts() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+
+        for sols in ["0.0000000001", "-1", "NaN"] {
+            let args = SendSolArgs {
+                sols: sols.to_string(),
+                ..args(None)
+            };
+            assert!(run(&rpc.client, &GlobalArgs::default(), &sender, args).is_err());
+        }
+        assert!(rpc.sent_transactions().is_empty());
+    }
 
-    println!("✅ - SOL sent, transaction signature: {}", signature);
-    println!("🔑 - Recipient: {}", args.recipient);
+    #[test]
+    fn sends_transfer() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let args = args(None);
+        let recipient = args.recipient.parse().unwrap();
 
-    Ok(())
+        let output = run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        let transaction = rpc.sent_transaction();
+        assert_instructions(
+            &transaction,
+            &sender.pubkey(),
+            &[solana_sdk::system_instruction::transfer(
+                &sender.pubkey(),
+                &recipient,
+                1_500_000_000,
+            )],
+        );
+        assert_eq!(
+            output.transaction.signature(),
+            Some(transaction.signatures[0].to_string().as_str())
+        );
+        assert_eq!(output.lamports, 1_500_000_000);
+    }
+
+    #[test]
+    fn appends_memo() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let args = args(Some("hello"));
+        let recipient = args.recipient.parse().unwrap();
+
+        run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[
+                solana_sdk::system_instruction::transfer(
+                    &sender.pubkey(),
+                    &recipient,
+                    1_500_000_000,
+                ),
+                Instruction::new_with_bytes(
+                    Pubkey::from_str(MEMO_PROGRAM).unwrap(),
+                    b"hello",
+                    vec![],
+                ),
+            ],
+        );
+    }
 }
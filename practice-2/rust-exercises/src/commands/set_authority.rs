@@ -0,0 +1,260 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token::{fetch_mint, resolve_token_account};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
+use anyhow::{bail, Result};
+use clap::{ArgGroup, Args, ValueEnum};
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use spl_token_2022::instruction::AuthorityType;
+
+#[derive(Args)]
+#[clap(group(ArgGroup::new("target").required(true).args(["new_authority", "revoke"])))]
+pub struct SetAuthorityArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    #[clap(long)]
+    pub mint: String,
+    #[clap(long, value_enum)]
+    pub authority_type: AuthorityKind,
+    #[clap(long)]
+    pub new_authority: Option<String>,
+    /// Remove the authority for good, e.g. revoking the mint authority fixes the supply
+    #[clap(long)]
+    pub revoke: bool,
+    /// Token account for owner and close authorities, defaults to the loaded keypair's
+    /// associated token account
+    #[clap(long)]
+    pub token_account: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AuthorityKind {
+    /// Mint authority of the mint
+    Mint,
+    /// Freeze authority of the mint
+    Freeze,
+    /// Owner of a token account
+    Owner,
+    /// Close authority of a token account
+    Close,
+}
+
+impl AuthorityKind {
+    fn authority_type(&self) -> AuthorityType {
+        match self {
+            AuthorityKind::Mint => AuthorityType::MintTokens,
+            AuthorityKind::Freeze => AuthorityType::FreezeAccount,
+            AuthorityKind::Owner => AuthorityType::AccountOwner,
+            AuthorityKind::Close => AuthorityType::CloseAccount,
+        }
+    }
+
+    fn is_mint_authority(&self) -> bool {
+        matches!(self, AuthorityKind::Mint | AuthorityKind::Freeze)
+    }
+}
+
+#[derive(Serialize)]
+pub struct SetAuthorityOutput {
+    /// Mint or token account whose authority changed
+    pub account: String,
+    pub authority_type: String,
+    /// `None` when the authority was revoked
+    pub new_authority: Option<String>,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for SetAuthorityOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Authority updated");
+        println!("🔑 - Account: {}", self.account);
+        match &self.new_authority {
+            Some(authority) => {
+                println!("👤 - New {} authority: {}", self.authority_type, authority)
+            }
+            None => println!("🚫 - {} authority revoked", self.authority_type),
+        }
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([ExplorerLink::Address(&self.account)]);
+        links.extend(self.new_authority.as_deref().map(ExplorerLink::Address));
+        links
+    }
+}
+
+pub fn execute(args: SetAuthorityArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    let sender = crate::commands::load_keypair(global)?;
+    let output = run(&client, global, &sender, args)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+/// Changes an authority held by the loaded keypair
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    args: SetAuthorityArgs,
+) -> Result<SetAuthorityOutput> {
+    if args.authority_type.is_mint_authority() && args.token_account.is_some() {
+        bail!("--token-account only applies to owner and close authorities");
+    }
+
+    let sender_pubkey = sender.pubkey();
+    let mint: Pubkey = args.mint.parse()?;
+    let new_authority: Option<Pubkey> =
+        args.new_authority.as_deref().map(str::parse).transpose()?;
+
+    let mint_info = fetch_mint(client, &mint)?;
+    let account = if args.authority_type.is_mint_authority() {
+        mint
+    } else {
+        resolve_token_account(
+            args.token_account.as_deref(),
+            &sender_pubkey,
+            &mint,
+            &mint_info.program_id,
+        )?
+    };
+
+    let instruction = spl_token_2022::instruction::set_authority(
+        &mint_info.program_id,
+        &account,
+        new_authority.as_ref(),
+        args.authority_type.authority_type(),
+        &sender_pubkey,
+        &[],
+    )?;
+
+    let transaction =
+        process_transaction(client, global, &[instruction], &sender_pubkey, &[sender])?;
+
+    Ok(SetAuthorityOutput {
+        account: account.to_string(),
+        authority_type: args
+            .authority_type
+            .to_possible_value()
+            .expect("authority kinds have names")
+            .get_name()
+            .to_string(),
+        new_authority: new_authority.map(|authority| authority.to_string()),
+        transaction,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        account_response, assert_instructions, mint_account, MockRpc,
+    };
+    use solana_client::rpc_request::RpcRequest;
+
+    fn rpc_with_mint(mint: &Pubkey, authority: &Pubkey) -> MockRpc {
+        let account = mint_account(&spl_token::ID, authority, 2);
+        MockRpc::with_mocks([(RpcRequest::GetAccountInfo, account_response(mint, &account))].into())
+    }
+
+    #[test]
+    fn revokes_mint_authority() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &sender.pubkey());
+        let args = SetAuthorityArgs {
+            cluster: String::new(),
+            mint: mint.to_string(),
+            authority_type: AuthorityKind::Mint,
+            new_authority: None,
+            revoke: true,
+            token_account: None,
+        };
+
+        let output = run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        assert_eq!(output.authority_type, "mint");
+        assert!(output.new_authority.is_none());
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[spl_token::instruction::set_authority(
+                &spl_token::ID,
+                &mint,
+                None,
+                spl_token::instruction::AuthorityType::MintTokens,
+                &sender.pubkey(),
+                &[],
+            )
+            .unwrap()],
+        );
+    }
+
+    #[test]
+    fn rejects_token_account_for_mint_authorities() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &sender.pubkey());
+        let args = SetAuthorityArgs {
+            cluster: String::new(),
+            mint: mint.to_string(),
+            authority_type: AuthorityKind::Freeze,
+            new_authority: Some(Pubkey::new_unique().to_string()),
+            revoke: false,
+            token_account: Some(Pubkey::new_unique().to_string()),
+        };
+
+        let error = run(&rpc.client, &GlobalArgs::default(), &sender, args)
+            .err()
+            .unwrap();
+
+        assert!(error.to_string().contains("--token-account only applies"));
+        assert!(rpc.sent_transactions().is_empty());
+    }
+
+    #[test]
+    fn transfers_account_ownership() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let new_owner = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &sender.pubkey());
+        let args = SetAuthorityArgs {
+            cluster: String::new(),
+            mint: mint.to_string(),
+            authority_type: AuthorityKind::Owner,
+            new_authority: Some(new_owner.to_string()),
+            revoke: false,
+            token_account: None,
+        };
+
+        run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[spl_token::instruction::set_authority(
+                &spl_token::ID,
+                &spl_associated_token_account::get_associated_token_address(
+                    &sender.pubkey(),
+                    &mint,
+                ),
+                Some(&new_owner),
+                spl_token::instruction::AuthorityType::AccountOwner,
+                &sender.pubkey(),
+                &[],
+            )
+            .unwrap()],
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token_metadata::{fetch_metadata, fetch_native_metadata_mint};
+use anyhow::{Context, Result};
+use clap::Args;
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Args)]
+pub struct ShowTokenMetadataArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    #[clap(long)]
+    pub token_mint: String,
+    /// Read the token metadata extension of a Token-2022 mint instead of a Metaplex account
+    #[clap(long)]
+    pub native_metadata: bool,
+}
+
+#[derive(Serialize)]
+pub struct TokenMetadataInfo {
+    pub mint: String,
+    pub metadata: String,
+    /// `None` once native metadata is made immutable
+    pub update_authority: Option<String>,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seller_fee_basis_points: Option<u16>,
+    pub is_mutable: bool,
+    /// Custom fields of native metadata
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub additional_metadata: Vec<(String, String)>,
+}
+
+impl CommandOutput for TokenMetadataInfo {
+    fn print_text(&self) {
+        println!("🔑 - Metadata account: {}", self.metadata);
+        println!("🏷️ - Name: {}", self.name);
+        println!("🔤 - Symbol: {}", self.symbol);
+        println!("🔗 - URI: {}", self.uri);
+        if let Some(update_authority) = &self.update_authority {
+            println!("👤 - Update authority: {}", update_authority);
+        }
+        println!("✏️ - Mutable: {}", self.is_mutable);
+        for (key, value) in &self.additional_metadata {
+            println!("📎 - {}: {}", key, value);
+        }
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = vec![
+            ExplorerLink::Address(&self.mint),
+            ExplorerLink::Address(&self.metadata),
+        ];
+        links.extend(self.update_authority.as_deref().map(ExplorerLink::Address));
+        links
+    }
+}
+
+pub fn execute(args: ShowTokenMetadataArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    let mint = args.token_mint.parse()?;
+    let output = if args.native_metadata {
+        run_native(&client, &mint)?
+    } else {
+        run(&client, &mint)?
+    };
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+pub fn run(client: &RpcClient, mint: &Pubkey) -> Result<TokenMetadataInfo> {
+    let (metadata_pda, metadata) = fetch_metadata(client, mint)?;
+
+    Ok(TokenMetadataInfo {
+        mint: mint.to_string(),
+        metadata: metadata_pda.to_string(),
+        update_authority: Some(metadata.update_authority.to_string()),
+        name: metadata.name,
+        symbol: metadata.symbol,
+        uri: metadata.uri,
+        seller_fee_basis_points: Some(metadata.seller_fee_basis_points),
+        is_mutable: metadata.is_mutable,
+        additional_metadata: Vec::new(),
+    })
+}
+
+/// Decodes the metadata stored in the TLV data of a Token-2022 mint
+pub fn run_native(client: &RpcClient, mint: &Pubkey) -> Result<TokenMetadataInfo> {
+    let metadata = fetch_native_metadata_mint(client, mint)?
+        .metadata
+        .with_context(|| format!("Mint {} has no metadata", mint))?;
+    let update_authority = Option::<Pubkey>::from(metadata.update_authority);
+
+    Ok(TokenMetadataInfo {
+        mint: mint.to_string(),
+        metadata: mint.to_string(),
+        update_authority: update_authority.map(|authority| authority.to_string()),
+        name: metadata.name,
+        symbol: metadata.symbol,
+        uri: metadata.uri,
+        seller_fee_basis_points: None,
+        is_mutable: update_authority.is_some(),
+        additional_metadata: metadata.additional_metadata,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        account_response, metadata_account, native_metadata_mint_account, MockRpc,
+    };
+    use mpl_token_metadata::accounts::Metadata;
+    use solana_client::rpc_request::RpcRequest;
+    use spl_token_metadata_interface::state::TokenMetadata;
+
+    #[test]
+    fn decodes_padded_metadata() {
+        let mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let metadata_pda = Metadata::find_pda(&mint).0;
+        let account = metadata_account(&mint, &authority, "Token", "TKN", "https://example.com");
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&metadata_pda, &account),
+            )]
+            .into(),
+        );
+
+        let info = run(&rpc.client, &mint).unwrap();
+
+        assert_eq!(info.metadata, metadata_pda.to_string());
+        assert_eq!(info.update_authority, Some(authority.to_string()));
+        assert_eq!(info.name, "Token");
+        assert_eq!(info.symbol, "TKN");
+        assert_eq!(info.uri, "https://example.com");
+        assert!(info.is_mutable);
+    }
+
+    #[test]
+    fn decodes_native_metadata() {
+        let mint = Pubkey::new_unique();
+        let metadata = TokenMetadata {
+            update_authority: None.try_into().unwrap(),
+            mint,
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            uri: "https://example.com".to_string(),
+            additional_metadata: vec![("rarity".to_string(), "common".to_string())],
+        };
+        let account = native_metadata_mint_account(&mint, &Pubkey::new_unique(), Some(&metadata));
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&mint, &account),
+            )]
+            .into(),
+        );
+
+        let info = run_native(&rpc.client, &mint).unwrap();
+
+        assert_eq!(info.metadata, mint.to_string());
+        assert_eq!(info.name, "Token");
+        assert!(info.update_authority.is_none());
+        assert!(!info.is_mutable);
+        assert_eq!(
+            info.additional_metadata,
+            [("rarity".to_string(), "common".to_string())]
+        );
+    }
+}
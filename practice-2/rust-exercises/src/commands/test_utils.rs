@@ -0,0 +1,264 @@
+use base64::prelude::{Engine, BASE64_STANDARD};
+use borsh::BorshSerialize;
+use mpl_token_metadata::accounts::Metadata;
+use mpl_token_metadata::types::Key;
+use serde_json::{json, Value};
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::client_error::Result;
+use solana_client::rpc_client::{RpcClient, RpcClientConfig};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::{Response, RpcResponseContext};
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_rpc_client::mock_sender::{MockSender, Mocks};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::Message;
+use solana_sdk::program_option::COption;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use spl_token_2022::extension::{ExtensionType, StateWithExtensionsMut};
+use spl_token_metadata_interface::state::TokenMetadata;
+use std::sync::{Arc, Mutex};
+
+/// Wraps the RPC mock sender and records every request so tests can inspect what was sent
+struct RecordingSender {
+    inner: MockSender,
+    requests: Arc<Mutex<Vec<(RpcRequest, Value)>>>,
+}
+
+#[async_trait::async_trait]
+impl RpcSender for RecordingSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> Result<Value> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push((request, params.clone()));
+        self.inner.send(request, params).await
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.inner.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.inner.url()
+    }
+}
+
+pub struct MockRpc {
+    pub client: RpcClient,
+    requests: Arc<Mutex<Vec<(RpcRequest, Value)>>>,
+}
+
+impl MockRpc {
+    pub fn new() -> Self {
+        Self::with_mocks(Mocks::default())
+    }
+
+    pub fn with_mocks(mocks: Mocks) -> Self {
+        let requests = Arc::default();
+        let sender = RecordingSender {
+            inner: MockSender::new_with_mocks("succeeds", mocks),
+            requests: Arc::clone(&requests),
+        };
+
+        Self {
+            client: RpcClient::new_sender(sender, RpcClientConfig::default()),
+            requests,
+        }
+    }
+
+    /// Transactions submitted through `sendTransaction`, in order
+    pub fn sent_transactions(&self) -> Vec<Transaction> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(request, _)| *request == RpcRequest::SendTransaction)
+            .map(|(_, params)| {
+                let encoded = params[0].as_str().expect("encoded transaction");
+                let data = BASE64_STANDARD.decode(encoded).expect("base64 transaction");
+                bincode::deserialize(&data).expect("serialized transaction")
+            })
+            .collect()
+    }
+
+    /// Parameters of every `request` sent, in order
+    pub fn params(&self, request: RpcRequest) -> Vec<Value> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(sent, _)| *sent == request)
+            .map(|(_, params)| params.clone())
+            .collect()
+    }
+
+    /// Account sizes the rent exempt minimum was queried for, in order
+    pub fn rent_queries(&self) -> Vec<usize> {
+        self.params(RpcRequest::GetMinimumBalanceForRentExemption)
+            .iter()
+            .map(|params| params[0].as_u64().expect("account size") as usize)
+            .collect()
+    }
+
+    /// The single transaction sent by the command under test
+    pub fn sent_transaction(&self) -> Transaction {
+        let mut transactions = self.sent_transactions();
+        assert_eq!(transactions.len(), 1, "expected exactly one transaction");
+        transactions.remove(0)
+    }
+}
+
+/// Asserts the transaction contains exactly `instructions`, paid for by `payer`
+pub fn assert_instructions(
+    transaction: &Transaction,
+    payer: &Pubkey,
+    instructions: &[Instruction],
+) {
+    let expected = Message::new_with_blockhash(
+        instructions,
+        Some(payer),
+        &transaction.message.recent_blockhash,
+    );
+    assert_eq!(transaction.message, expected);
+    transaction.verify().expect("transaction is fully signed");
+}
+
+/// `getAccountInfo` response returning `account`
+pub fn account_response(address: &Pubkey, account: &Account) -> Value {
+    json!(Response {
+        context: RpcResponseContext {
+            slot: 1,
+            api_version: None
+        },
+        value: Some(UiAccount::encode(
+            address,
+            account,
+            UiAccountEncoding::Base64,
+            None,
+            None,
+        )),
+    })
+}
+
+/// Initialized mint owned by `program_id`, with `authority` as mint and freeze authority
+pub fn mint_account(program_id: &Pubkey, authority: &Pubkey, decimals: u8) -> Account {
+    let mint = spl_token::state::Mint {
+        mint_authority: COption::Some(*authority),
+        supply: 0,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::Some(*authority),
+    };
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    mint.pack_into_slice(&mut data);
+
+    Account {
+        lamports: 1,
+        data,
+        owner: *program_id,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Initialized token account of `mint` owned by `owner`, under `program_id`
+pub fn token_account(program_id: &Pubkey, mint: &Pubkey, owner: &Pubkey) -> Account {
+    let account = spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        state: spl_token::state::AccountState::Initialized,
+        ..spl_token::state::Account::default()
+    };
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    account.pack_into_slice(&mut data);
+
+    Account {
+        lamports: 1,
+        data,
+        owner: *program_id,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Metadata account of `mint`, with strings padded the way the token metadata program stores them
+pub fn metadata_account(
+    mint: &Pubkey,
+    update_authority: &Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Account {
+    let metadata = Metadata {
+        key: Key::MetadataV1,
+        update_authority: *update_authority,
+        mint: *mint,
+        name: format!("{:\0<32}", name),
+        symbol: format!("{:\0<10}", symbol),
+        uri: format!("{:\0<200}", uri),
+        seller_fee_basis_points: 0,
+        creators: None,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: None,
+        collection: None,
+        uses: None,
+        collection_details: None,
+        programmable_config: None,
+    };
+
+    Account {
+        lamports: 1,
+        data: metadata.try_to_vec().expect("metadata serializes"),
+        owner: mpl_token_metadata::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Token-2022 mint with `authority` as mint authority and a metadata pointer to itself, holding
+/// `metadata` in its token metadata extension when given
+pub fn native_metadata_mint_account(
+    mint: &Pubkey,
+    authority: &Pubkey,
+    metadata: Option<&TokenMetadata>,
+) -> Account {
+    let base_len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::MetadataPointer,
+    ])
+    .unwrap();
+    let metadata_len = metadata.map_or(0, |metadata| metadata.tlv_size_of().unwrap());
+    let mut data = vec![0; base_len + metadata_len];
+
+    let mut state =
+        StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(&mut data)
+            .unwrap();
+    let pointer = state.init_extension::<MetadataPointer>(true).unwrap();
+    pointer.authority = Some(*authority).try_into().unwrap();
+    pointer.metadata_address = Some(*mint).try_into().unwrap();
+    state.base = spl_token_2022::state::Mint {
+        mint_authority: COption::Some(*authority),
+        supply: 0,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    if let Some(metadata) = metadata {
+        state.init_variable_len_extension(metadata, false).unwrap();
+    }
+
+    Account {
+        lamports: 1,
+        data,
+        owner: spl_token_2022::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
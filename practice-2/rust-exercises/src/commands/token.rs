@@ -0,0 +1,153 @@
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+
+/// Selects the token program a command works with
+#[derive(Args, Clone, Copy, Default)]
+#[clap(about = None, long_about = None)]
+pub struct TokenProgramArgs {
+    /// Use the Token-2022 program instead of the original token program
+    #[clap(long)]
+    pub token_2022: bool,
+}
+
+impl TokenProgramArgs {
+    pub fn program_id(&self) -> Pubkey {
+        if self.token_2022 {
+            spl_token_2022::ID
+        } else {
+            spl_token::ID
+        }
+    }
+}
+
+/// On-chain facts about a mint that instructions depend on
+pub struct MintInfo {
+    pub program_id: Pubkey,
+    pub decimals: u8,
+    /// Token-2022 extensions of the mint, empty for original mints
+    pub extensions: Vec<ExtensionType>,
+}
+
+/// Fetches a mint of either token program
+pub fn fetch_mint(client: &RpcClient, mint: &Pubkey) -> Result<MintInfo> {
+    let account = client
+        .get_account(mint)
+        .with_context(|| format!("Failed to fetch mint {}", mint))?;
+    if account.owner != spl_token::ID && account.owner != spl_token_2022::ID {
+        bail!("{} is not owned by a token program", mint);
+    }
+
+    // Token-2022 mints start with the same layout as original ones
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data)
+        .with_context(|| format!("{} is not a token mint", mint))?;
+
+    Ok(MintInfo {
+        program_id: account.owner,
+        decimals: state.base.decimals,
+        extensions: state.get_extension_types()?,
+    })
+}
+
+/// Token account a command acts on: `token_account` when given, otherwise the associated
+/// token account of `owner`
+pub fn resolve_token_account(
+    token_account: Option<&str>,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Pubkey> {
+    match token_account {
+        Some(token_account) => Ok(token_account.parse()?),
+        None => Ok(
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                owner, mint, program_id,
+            ),
+        ),
+    }
+}
+
+/// Decimals of SOL amounts, whose base units are lamports
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Converts a decimal token amount like `12.34` into base units of a mint with `decimals`
+pub fn parse_token_amount(amount: &str, decimals: u8) -> Result<u64> {
+    let amount = amount.trim();
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+        bail!("Invalid token amount '{}'", amount);
+    }
+    if fraction.len() > decimals as usize {
+        bail!(
+            "Token amount '{}' has more decimal places than the mint's {} decimals",
+            amount,
+            decimals
+        );
+    }
+
+    let too_large = || anyhow::anyhow!("Token amount '{}' is too large", amount);
+    let parse = |part: &str| -> Result<u64> {
+        if part.is_empty() {
+            Ok(0)
+        } else {
+            part.parse().map_err(|_| too_large())
+        }
+    };
+
+    // Mints may have more decimals than 10^decimals fits in a u64, zero never needs scaling
+    let scale = |value: u64, digits: usize| match value {
+        0 => Some(0),
+        value => 10_u64
+            .checked_pow(digits as u32)
+            .and_then(|scale| value.checked_mul(scale)),
+    };
+    let whole_units = scale(parse(whole)?, decimals as usize);
+    let fraction_units = scale(parse(fraction)?, decimals as usize - fraction.len());
+    whole_units
+        .zip(fraction_units)
+        .and_then(|(whole_units, fraction_units)| whole_units.checked_add(fraction_units))
+        .ok_or_else(too_large)
+}
+
+/// Formats base units of a mint with `decimals` as a decimal amount
+pub fn format_token_amount(amount: u64, decimals: u8) -> String {
+    spl_token::amount_to_ui_amount_string_trimmed(amount, decimals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_decimal_amounts() {
+        assert_eq!(parse_token_amount("12.34", 2).unwrap(), 1234);
+        assert_eq!(parse_token_amount("12", 2).unwrap(), 1200);
+        assert_eq!(parse_token_amount("0.5", 9).unwrap(), 500_000_000);
+        assert_eq!(parse_token_amount(".5", 1).unwrap(), 5);
+        assert_eq!(parse_token_amount("7", 0).unwrap(), 7);
+        assert_eq!(format_token_amount(1234, 2), "12.34");
+    }
+
+    #[test]
+    fn rejects_invalid_amounts() {
+        assert!(parse_token_amount("1.234", 2).is_err());
+        assert!(parse_token_amount("-1", 2).is_err());
+        assert!(parse_token_amount("1e3", 2).is_err());
+        assert!(parse_token_amount(".", 2).is_err());
+        assert!(parse_token_amount("", 2).is_err());
+        assert!(parse_token_amount("18446744073709551616", 0).is_err());
+        assert!(parse_token_amount("18446744073709551615", 1).is_err());
+    }
+
+    #[test]
+    fn rejects_amounts_overflowing_large_decimals() {
+        let error = parse_token_amount("0.5", 20).unwrap_err();
+        assert!(error.to_string().contains("too large"));
+        assert!(parse_token_amount("1", 255).is_err());
+        assert_eq!(parse_token_amount("0.00000000000000000001", 20).unwrap(), 1);
+        assert_eq!(parse_token_amount("0", 255).unwrap(), 0);
+    }
+}
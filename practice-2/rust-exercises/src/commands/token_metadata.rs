@@ -0,0 +1,141 @@
+use anyhow::{bail, Context, Result};
+use mpl_token_metadata::accounts::Metadata;
+use mpl_token_metadata::{MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use spl_token_metadata_interface::state::TokenMetadata;
+
+/// Checks the length limits enforced by the token metadata program, so a too long field fails
+/// before a transaction is sent
+pub fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    for (field, value, limit) in [
+        ("Name", name, MAX_NAME_LENGTH),
+        ("Symbol", symbol, MAX_SYMBOL_LENGTH),
+        ("URI", uri, MAX_URI_LENGTH),
+    ] {
+        if value.len() > limit {
+            bail!(
+                "{} is {} bytes long, the limit is {} bytes",
+                field,
+                value.len(),
+                limit
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Decodes a metadata account, trimming the padding the program stores strings with
+pub fn decode_metadata(data: &[u8]) -> Result<Metadata> {
+    let mut metadata = Metadata::from_bytes(data).context("Failed to decode metadata account")?;
+    for field in [&mut metadata.name, &mut metadata.symbol, &mut metadata.uri] {
+        *field = field.trim_end_matches('\0').to_string();
+    }
+    Ok(metadata)
+}
+
+/// Fetches the metadata PDA of `mint`
+pub fn fetch_metadata(client: &RpcClient, mint: &Pubkey) -> Result<(Pubkey, Metadata)> {
+    let (metadata_pda, _) = Metadata::find_pda(mint);
+    let account = client
+        .get_account(&metadata_pda)
+        .with_context(|| format!("No token metadata found for mint {}", mint))?;
+    if account.owner != mpl_token_metadata::ID {
+        bail!(
+            "Account {} is not owned by the token metadata program",
+            metadata_pda
+        );
+    }
+
+    Ok((metadata_pda, decode_metadata(&account.data)?))
+}
+
+/// Token-2022 mint whose metadata pointer points at the mint itself, so the token metadata
+/// extension holds its metadata
+pub struct NativeMetadataMint {
+    pub account: Account,
+    /// `None` until the metadata is initialized
+    pub metadata: Option<TokenMetadata>,
+}
+
+impl NativeMetadataMint {
+    /// Lamport transfer topping the mint up to the rent exempt minimum of the largest of the
+    /// metadata `states` stored one instruction after another, as the token program
+    /// reallocates the mint on each of them without funding it
+    pub fn rent_top_up(
+        &self,
+        client: &RpcClient,
+        payer: &Pubkey,
+        mint: &Pubkey,
+        states: &[TokenMetadata],
+    ) -> Result<Option<Instruction>> {
+        let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&self.account.data)?;
+        let mut new_len = self.account.data.len();
+        for metadata in states {
+            new_len =
+                new_len.max(state.try_get_new_account_len_for_variable_len_extension(metadata)?);
+        }
+        let rent = client.get_minimum_balance_for_rent_exemption(new_len)?;
+
+        Ok((rent > self.account.lamports).then(|| {
+            solana_sdk::system_instruction::transfer(payer, mint, rent - self.account.lamports)
+        }))
+    }
+}
+
+/// Fetches a mint that can carry native Token-2022 metadata
+pub fn fetch_native_metadata_mint(client: &RpcClient, mint: &Pubkey) -> Result<NativeMetadataMint> {
+    let account = client
+        .get_account(mint)
+        .with_context(|| format!("Failed to fetch mint {}", mint))?;
+    if account.owner != spl_token_2022::ID {
+        bail!(
+            "Native metadata needs a Token-2022 mint, {} is not one",
+            mint
+        );
+    }
+
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data)
+        .with_context(|| format!("{} is not a token mint", mint))?;
+    let points_at_mint = state
+        .get_extension::<MetadataPointer>()
+        .map(|pointer| Option::<Pubkey>::from(pointer.metadata_address) == Some(*mint))
+        .unwrap_or(false);
+    if !points_at_mint {
+        bail!(
+            "Metadata pointer of {} doesn't point at the mint, create it with --metadata-pointer",
+            mint
+        );
+    }
+    let metadata = state.get_variable_len_extension::<TokenMetadata>().ok();
+
+    Ok(NativeMetadataMint { account, metadata })
+}
+
+/// Parses a `KEY=VALUE` pair, the value may contain further `=`
+pub fn parse_key_value(value: &str) -> Result<(String, String)> {
+    let (key, value) = value.split_once('=').context("Expected KEY=VALUE")?;
+    Ok((key.to_string(), value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enforces_length_limits() {
+        assert!(validate_metadata("Token", "TKN", "https://example.com/token.json").is_ok());
+
+        let error = validate_metadata(&"n".repeat(33), "TKN", "").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Name is 33 bytes long, the limit is 32 bytes"
+        );
+        assert!(validate_metadata("Token", "SYMBOL12345", "").is_err());
+        assert!(validate_metadata("Token", "TKN", &"u".repeat(201)).is_err());
+    }
+}
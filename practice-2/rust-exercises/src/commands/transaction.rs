@@ -0,0 +1,819 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::ExplorerLink;
+use crate::commands::program_error::describe_transaction_error;
+use crate::commands::read_keypair_file;
+use anyhow::{bail, Context, Result};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use clap::ValueEnum;
+use serde::Serialize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::nonce_utils;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcSendTransactionConfig, RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_client::rpc_request::RpcError;
+use solana_sdk::commitment_config::{CommitmentConfig, CommitmentLevel};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::Message;
+use solana_sdk::nonce;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::sanitize::Sanitize;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use std::time::Duration;
+
+/// Accounts `getRecentPrioritizationFees` accepts per request
+const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+
+/// Delay before the first resend after a network error, doubled on every further attempt
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Commitment level selected with `--commitment`
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Commitment {
+    Processed,
+    Confirmed,
+    #[default]
+    Finalized,
+}
+
+impl Commitment {
+    pub fn config(self) -> CommitmentConfig {
+        let commitment = match self {
+            Commitment::Processed => CommitmentLevel::Processed,
+            Commitment::Confirmed => CommitmentLevel::Confirmed,
+            Commitment::Finalized => CommitmentLevel::Finalized,
+        };
+        CommitmentConfig { commitment }
+    }
+}
+
+/// Why a failed send may be attempted again
+#[derive(Debug, PartialEq, Eq)]
+enum Retry {
+    /// The blockhash expired before the transaction landed, so it can't land anymore and
+    /// has to be signed again with a new one
+    Resign,
+    /// The request failed on the way, the same transaction is sent again
+    Resend,
+}
+
+/// What happened to a transaction built by a command
+#[derive(Serialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum TransactionOutcome {
+    Sent {
+        signature: String,
+    },
+    Simulated(SimulationReport),
+    SignedOnly {
+        signature: String,
+        base58: String,
+        base64: String,
+    },
+}
+
+#[derive(Serialize)]
+pub struct SimulationReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    /// Fee in lamports for the transaction message
+    pub fee: u64,
+    pub balance_changes: Vec<BalanceChange>,
+}
+
+#[derive(Serialize)]
+pub struct BalanceChange {
+    pub address: String,
+    pub before: u64,
+    pub after: u64,
+    pub change: i128,
+}
+
+impl TransactionOutcome {
+    pub fn signature(&self) -> Option<&str> {
+        match self {
+            TransactionOutcome::Sent { signature, .. } => Some(signature),
+            _ => None,
+        }
+    }
+
+    /// Link to the signature of a sent transaction, signed only or simulated ones never land
+    pub fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        self.signature().map(ExplorerLink::Tx).into_iter().collect()
+    }
+
+    /// Prints the outcome, using `sent_message` as the headline of a sent transaction
+    pub fn print_text(&self, sent_message: &str) {
+        match self {
+            TransactionOutcome::Sent { signature, .. } => {
+                println!(
+                    "✅ - {}, transaction signature: {}",
+                    sent_message, signature
+                )
+            }
+            TransactionOutcome::Simulated(report) => report.print_text(),
+            TransactionOutcome::SignedOnly {
+                signature,
+                base58,
+                base64,
+            } => {
+                println!("✍️ - Transaction signed, not sent: {}", signature);
+                println!("📦 - Base58: {}", base58);
+                println!("📦 - Base64: {}", base64);
+            }
+        }
+    }
+}
+
+impl SimulationReport {
+    fn print_text(&self) {
+        match &self.error {
+            Some(error) => println!("❌ - Simulation failed: {}", error),
+            None => println!("🧪 - Simulation succeeded"),
+        }
+        if let Some(units) = self.units_consumed {
+            println!("⚙️ - Compute units consumed: {}", units);
+        }
+        println!("💸 - Fee: {} lamports", self.fee);
+        for change in &self.balance_changes {
+            println!(
+                "💰 - {}: {} -> {} ({:+} lamports)",
+                change.address, change.before, change.after, change.change
+            );
+        }
+        println!("📜 - Logs:");
+        for log in &self.logs {
+            println!("    {}", log);
+        }
+    }
+}
+
+/// Signs the instructions and sends them, or only simulates / signs them when
+/// `--dry-run` / `--sign-only` is set. With `--nonce` the transaction advances the
+/// durable nonce and uses it in place of a recent blockhash, so it stays valid until sent.
+/// Failed sends are retried up to `--max-retries` times, re-signing once the blockhash expired
+pub fn process_transaction(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &[&dyn Signer],
+) -> Result<TransactionOutcome> {
+    let nonce_authority = load_nonce_authority(global)?;
+    // The same keypair may fill several roles, e.g. fee payer and multisig signer
+    let mut unique_signers: Vec<&dyn Signer> = Vec::new();
+    let nonce_signer = nonce_authority
+        .as_ref()
+        .map(|authority| authority as &dyn Signer);
+    for signer in signers.iter().copied().chain(nonce_signer) {
+        if !unique_signers
+            .iter()
+            .any(|unique| unique.pubkey() == signer.pubkey())
+        {
+            unique_signers.push(signer);
+        }
+    }
+
+    let nonce_authority_pubkey = nonce_authority
+        .as_ref()
+        .map_or(*payer, |authority| authority.pubkey());
+    let sign = || -> Result<Transaction> {
+        let message = build_message(client, global, instructions, payer, &nonce_authority_pubkey)?;
+        let recent_blockhash = message.recent_blockhash;
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction.try_sign(&unique_signers, recent_blockhash)?;
+        Ok(transaction)
+    };
+
+    let mut transaction = sign()?;
+    let mut attempt = 0;
+    loop {
+        let error = match dispatch_transaction(client, global, &transaction) {
+            Ok(outcome) => return Ok(outcome),
+            Err(error) if attempt >= global.max_retries => return Err(error),
+            Err(error) => error,
+        };
+        attempt += 1;
+        match retry_kind(&error, global) {
+            Some(Retry::Resign) => {
+                global.output.status(format!(
+                    "Blockhash expired, signing again ({}/{})",
+                    attempt, global.max_retries
+                ));
+                transaction = sign()?;
+            }
+            Some(Retry::Resend) => {
+                global.output.status(format!(
+                    "{}, sending again ({}/{})",
+                    error, attempt, global.max_retries
+                ));
+                std::thread::sleep(RETRY_BACKOFF * 2u32.pow(attempt - 1));
+            }
+            None => return Err(error),
+        }
+    }
+}
+
+/// Whether the failed send behind `error` may be retried, and how
+fn retry_kind(error: &anyhow::Error, global: &GlobalArgs) -> Option<Retry> {
+    let error = error.downcast_ref::<ClientError>()?;
+    if let Some(transaction_error) = error.get_transaction_error() {
+        // A nonce transaction doesn't expire, its blockhash only changes once the nonce advances
+        return (transaction_error == TransactionError::BlockhashNotFound
+            && global.nonce.is_none())
+        .then_some(Retry::Resign);
+    }
+    match error.kind() {
+        // Raised by the confirmation spinner once the blockhash is no longer valid
+        ClientErrorKind::RpcError(RpcError::ForUser(message))
+            if message.starts_with("unable to confirm transaction") && global.nonce.is_none() =>
+        {
+            Some(Retry::Resign)
+        }
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => Some(Retry::Resend),
+        _ => None,
+    }
+}
+
+/// Builds the message for `instructions`, prepending the nonce advance when `--nonce` is set
+/// and the compute budget instructions of `--compute-unit-limit` and the priority fee options
+pub fn build_message(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    nonce_authority: &Pubkey,
+) -> Result<Message> {
+    let mut all_instructions = Vec::new();
+    let blockhash = match &global.nonce {
+        None => {
+            client
+                .get_latest_blockhash_with_commitment(global.commitment.config())?
+                .0
+        }
+        Some(nonce) => {
+            let nonce = nonce.parse()?;
+            let nonce_data = fetch_nonce(client, &nonce)?;
+            if nonce_data.authority != *nonce_authority {
+                bail!(
+                    "Nonce account {} is controlled by {}, not {}",
+                    nonce,
+                    nonce_data.authority,
+                    nonce_authority
+                );
+            }
+            // The nonce advance has to be the first instruction
+            all_instructions.push(system_instruction::advance_nonce_account(
+                &nonce,
+                nonce_authority,
+            ));
+            nonce_data.blockhash()
+        }
+    };
+
+    all_instructions.extend(compute_budget_instructions(client, global, instructions)?);
+    all_instructions.extend_from_slice(instructions);
+    Ok(Message::new_with_blockhash(
+        &all_instructions,
+        Some(payer),
+        &blockhash,
+    ))
+}
+
+fn compute_budget_instructions(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    instructions: &[Instruction],
+) -> Result<Vec<Instruction>> {
+    let mut budget = Vec::new();
+    if let Some(units) = global.compute_unit_limit {
+        budget.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+    }
+
+    let priority_fee = match global.auto_priority_fee {
+        Some(percentile) => Some(recent_priority_fee(client, instructions, percentile)?),
+        None => global.priority_fee,
+    };
+    if let Some(micro_lamports) = priority_fee.filter(|&fee| fee > 0) {
+        budget.push(ComputeBudgetInstruction::set_compute_unit_price(
+            micro_lamports,
+        ));
+    }
+    Ok(budget)
+}
+
+/// Priority fee at `percentile` of the fees paid in recent slots by transactions writing
+/// the accounts `instructions` write, 0 when there were none
+fn recent_priority_fee(
+    client: &RpcClient,
+    instructions: &[Instruction],
+    percentile: u8,
+) -> Result<u64> {
+    let mut writable_accounts: Vec<Pubkey> = Vec::new();
+    for account in instructions.iter().flat_map(|ix| &ix.accounts) {
+        if account.is_writable && !writable_accounts.contains(&account.pubkey) {
+            writable_accounts.push(account.pubkey);
+        }
+    }
+    writable_accounts.truncate(MAX_PRIORITIZATION_FEE_ACCOUNTS);
+
+    let mut fees: Vec<u64> = client
+        .get_recent_prioritization_fees(&writable_accounts)
+        .context("Failed to fetch recent prioritization fees")?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+    fees.sort_unstable();
+
+    Ok(match fees.len() {
+        0 => 0,
+        len => fees[(len - 1) * percentile as usize / 100],
+    })
+}
+
+/// Sends an already signed transaction, honouring `--dry-run`, `--sign-only`, `--commitment`
+/// and `--skip-preflight`. A failing program is named in the error, together with the
+/// SPL token, system program or Anchor error its custom error code stands for
+pub fn dispatch_transaction(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    transaction: &Transaction,
+) -> Result<TransactionOutcome> {
+    if global.dry_run {
+        return Ok(TransactionOutcome::Simulated(simulate(
+            client,
+            transaction,
+        )?));
+    }
+
+    if global.sign_only {
+        let serialized = bincode::serialize(transaction)?;
+        return Ok(TransactionOutcome::SignedOnly {
+            signature: transaction.signatures[0].to_string(),
+            base58: solana_sdk::bs58::encode(&serialized).into_string(),
+            base64: BASE64_STANDARD.encode(&serialized),
+        });
+    }
+
+    let commitment = global.commitment.config();
+    let config = RpcSendTransactionConfig {
+        skip_preflight: global.skip_preflight,
+        preflight_commitment: Some(commitment.commitment),
+        ..RpcSendTransactionConfig::default()
+    };
+    let signature = client
+        .send_and_confirm_transaction_with_spinner_and_config(transaction, commitment, config)
+        .map_err(|error| match error.get_transaction_error() {
+            Some(transaction_error) => {
+                let description =
+                    describe_transaction_error(&transaction_error, &transaction.message);
+                anyhow::Error::new(error).context(format!("Transaction failed: {}", description))
+            }
+            None => error.into(),
+        })?;
+
+    Ok(TransactionOutcome::Sent {
+        signature: signature.to_string(),
+    })
+}
+
+/// Decodes a transaction printed by `--sign-only`, accepting base64 or base58
+pub fn decode_transaction(encoded: &str) -> Result<Transaction> {
+    let encoded = encoded.trim();
+    // A base58 string can also be valid base64, so fall back when it doesn't deserialize
+    let from_base64 = BASE64_STANDARD
+        .decode(encoded)
+        .ok()
+        .and_then(|data| bincode::deserialize(&data).ok());
+    let transaction: Transaction = match from_base64 {
+        Some(transaction) => transaction,
+        None => {
+            let data = solana_sdk::bs58::decode(encoded)
+                .into_vec()
+                .context("Transaction is neither base64 nor base58")?;
+            bincode::deserialize(&data).context("Failed to deserialize transaction")?
+        }
+    };
+
+    // Instructions index the account keys, out of range indexes would panic later on
+    transaction.sanitize().context("Transaction is malformed")?;
+    Ok(transaction)
+}
+
+/// Keypair passed with `--nonce-authority`, `None` when the fee payer is the authority
+pub fn load_nonce_authority(global: &GlobalArgs) -> Result<Option<Keypair>> {
+    global
+        .nonce_authority
+        .as_deref()
+        .map(read_keypair_file)
+        .transpose()
+}
+
+pub fn fetch_nonce(client: &RpcClient, nonce: &Pubkey) -> Result<nonce::state::Data> {
+    let account = nonce_utils::get_account(client, nonce)
+        .with_context(|| format!("Failed to fetch nonce account {}", nonce))?;
+    nonce_utils::data_from_account(&account)
+        .with_context(|| format!("{} is not an initialized nonce account", nonce))
+}
+
+fn simulate(client: &RpcClient, transaction: &Transaction) -> Result<SimulationReport> {
+    let message = &transaction.message;
+    let writable_accounts: Vec<Pubkey> = message
+        .account_keys
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| message.is_writable(*index))
+        .map(|(_, address)| *address)
+        .collect();
+
+    let accounts_before = client
+        .get_multiple_accounts(&writable_accounts)
+        .context("Failed to fetch account balances")?;
+
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: true,
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: writable_accounts.iter().map(Pubkey::to_string).collect(),
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = client
+        .simulate_transaction_with_config(transaction, config)
+        .context("Failed to simulate transaction")?
+        .value;
+    let fee = client
+        .get_fee_for_message(message)
+        .context("Failed to estimate fee")?;
+
+    let accounts_after = result.accounts.unwrap_or_default();
+    let balance_changes = writable_accounts
+        .iter()
+        .zip(accounts_before)
+        .zip(accounts_after)
+        .map(|((address, before), after)| {
+            let before = before.map_or(0, |account| account.lamports);
+            let after = after.map_or(0, |account| account.lamports);
+            BalanceChange {
+                address: address.to_string(),
+                before,
+                after,
+                change: after as i128 - before as i128,
+            }
+        })
+        .filter(|change| change.change != 0)
+        .collect();
+
+    Ok(SimulationReport {
+        error: result
+            .err
+            .map(|err| describe_transaction_error(&err, message)),
+        logs: result.logs.unwrap_or_default(),
+        units_consumed: result.units_consumed,
+        fee,
+        balance_changes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{assert_instructions, MockRpc};
+    use serde_json::json;
+    use solana_account_decoder::UiAccount;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_client::rpc_response::{
+        Response, RpcPrioritizationFee, RpcResponseContext, RpcSimulateTransactionResult,
+    };
+    use solana_sdk::account::Account;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::nonce::state::{Data, DurableNonce, State, Versions};
+
+    fn response<T: Serialize>(value: T) -> serde_json::Value {
+        json!(Response {
+            context: RpcResponseContext {
+                slot: 1,
+                api_version: None
+            },
+            value,
+        })
+    }
+
+    fn ui_account(address: &Pubkey, lamports: u64) -> UiAccount {
+        UiAccount::encode(
+            address,
+            &Account::new(lamports, 0, &solana_sdk::system_program::ID),
+            UiAccountEncoding::Base64,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn dry_run_simulates_instead_of_sending() {
+        let payer = Keypair::new();
+        let recipient = Pubkey::new_unique();
+        let mocks = [
+            (
+                RpcRequest::GetMultipleAccounts,
+                response([
+                    Some(ui_account(&payer.pubkey(), 1_000_000)),
+                    None::<UiAccount>,
+                ]),
+            ),
+            (
+                RpcRequest::SimulateTransaction,
+                response(RpcSimulateTransactionResult {
+                    err: None,
+                    logs: Some(vec!["Program log: ok".to_string()]),
+                    accounts: Some(vec![
+                        Some(ui_account(&payer.pubkey(), 994_000)),
+                        Some(ui_account(&recipient, 1_000)),
+                    ]),
+                    units_consumed: Some(150),
+                    return_data: None,
+                    inner_instructions: None,
+                }),
+            ),
+        ]
+        .into();
+        let rpc = MockRpc::with_mocks(mocks);
+        let global = GlobalArgs {
+            dry_run: true,
+            ..GlobalArgs::default()
+        };
+        let instruction =
+            solana_sdk::system_instruction::transfer(&payer.pubkey(), &recipient, 1_000);
+
+        let outcome = process_transaction(
+            &rpc.client,
+            &global,
+            &[instruction],
+            &payer.pubkey(),
+            &[&payer],
+        )
+        .unwrap();
+
+        assert!(rpc.sent_transactions().is_empty());
+        let TransactionOutcome::Simulated(report) = outcome else {
+            panic!("expected a simulation report");
+        };
+        assert_eq!(report.logs, ["Program log: ok"]);
+        assert_eq!(report.units_consumed, Some(150));
+        let changes: Vec<_> = report
+            .balance_changes
+            .iter()
+            .map(|change| (change.address.clone(), change.change))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                (payer.pubkey().to_string(), -6_000),
+                (recipient.to_string(), 1_000)
+            ]
+        );
+    }
+
+    #[test]
+    fn sign_only_returns_serialized_transaction() {
+        let rpc = MockRpc::new();
+        let payer = Keypair::new();
+        let global = GlobalArgs {
+            sign_only: true,
+            ..GlobalArgs::default()
+        };
+        let instruction =
+            solana_sdk::system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+
+        let outcome = process_transaction(
+            &rpc.client,
+            &global,
+            &[instruction],
+            &payer.pubkey(),
+            &[&payer],
+        )
+        .unwrap();
+
+        assert!(rpc.sent_transactions().is_empty());
+        let TransactionOutcome::SignedOnly { base58, base64, .. } = outcome else {
+            panic!("expected a signed transaction");
+        };
+        let from_base64 = BASE64_STANDARD.decode(base64).unwrap();
+        assert_eq!(
+            solana_sdk::bs58::decode(base58).into_vec().unwrap(),
+            from_base64
+        );
+        let transaction: Transaction = bincode::deserialize(&from_base64).unwrap();
+        transaction.verify().unwrap();
+    }
+
+    #[test]
+    fn advances_durable_nonce() {
+        let payer = Keypair::new();
+        let nonce = Pubkey::new_unique();
+        let durable_nonce = DurableNonce::from_blockhash(&Hash::new_unique());
+        let nonce_account = Account::new_data(
+            1_000_000,
+            &Versions::new(State::Initialized(Data::new(
+                payer.pubkey(),
+                durable_nonce,
+                5_000,
+            ))),
+            &solana_sdk::system_program::ID,
+        )
+        .unwrap();
+        let mocks = [(
+            RpcRequest::GetAccountInfo,
+            response(Some(UiAccount::encode(
+                &nonce,
+                &nonce_account,
+                UiAccountEncoding::Base64,
+                None,
+                None,
+            ))),
+        )]
+        .into();
+        let rpc = MockRpc::with_mocks(mocks);
+        let global = GlobalArgs {
+            nonce: Some(nonce.to_string()),
+            ..GlobalArgs::default()
+        };
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000);
+
+        process_transaction(
+            &rpc.client,
+            &global,
+            std::slice::from_ref(&transfer),
+            &payer.pubkey(),
+            &[&payer],
+        )
+        .unwrap();
+
+        let transaction = rpc.sent_transaction();
+        assert_eq!(
+            transaction.message.recent_blockhash,
+            *durable_nonce.as_hash()
+        );
+        assert_instructions(
+            &transaction,
+            &payer.pubkey(),
+            &[
+                system_instruction::advance_nonce_account(&nonce, &payer.pubkey()),
+                transfer,
+            ],
+        );
+    }
+
+    #[test]
+    fn prepends_compute_budget_instructions() {
+        let rpc = MockRpc::new();
+        let payer = Keypair::new();
+        let global = GlobalArgs {
+            compute_unit_limit: Some(300_000),
+            priority_fee: Some(5_000),
+            ..GlobalArgs::default()
+        };
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+
+        process_transaction(
+            &rpc.client,
+            &global,
+            std::slice::from_ref(&transfer),
+            &payer.pubkey(),
+            &[&payer],
+        )
+        .unwrap();
+
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &payer.pubkey(),
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(300_000),
+                ComputeBudgetInstruction::set_compute_unit_price(5_000),
+                transfer,
+            ],
+        );
+    }
+
+    #[test]
+    fn derives_priority_fee_from_recent_fees() {
+        let fees: Vec<_> = [300, 100, 1_000, 200]
+            .into_iter()
+            .map(|prioritization_fee| RpcPrioritizationFee {
+                slot: 1,
+                prioritization_fee,
+            })
+            .collect();
+        let rpc =
+            MockRpc::with_mocks([(RpcRequest::GetRecentPrioritizationFees, json!(fees))].into());
+        let payer = Keypair::new();
+        let global = GlobalArgs {
+            auto_priority_fee: Some(50),
+            ..GlobalArgs::default()
+        };
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+
+        process_transaction(
+            &rpc.client,
+            &global,
+            std::slice::from_ref(&transfer),
+            &payer.pubkey(),
+            &[&payer],
+        )
+        .unwrap();
+
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &payer.pubkey(),
+            &[
+                ComputeBudgetInstruction::set_compute_unit_price(200),
+                transfer,
+            ],
+        );
+    }
+
+    #[test]
+    fn retries_expired_and_failed_sends() {
+        let global = GlobalArgs::default();
+        let expired = anyhow::Error::new(ClientError::from(ClientErrorKind::RpcError(
+            RpcError::ForUser("unable to confirm transaction. This can happen...".to_string()),
+        )));
+        let network = anyhow::Error::new(ClientError::from(ClientErrorKind::Io(
+            std::io::ErrorKind::ConnectionReset.into(),
+        )));
+        let program = anyhow::Error::new(ClientError::from(ClientErrorKind::TransactionError(
+            TransactionError::InsufficientFundsForFee,
+        )));
+
+        assert_eq!(retry_kind(&expired, &global), Some(Retry::Resign));
+        assert_eq!(retry_kind(&network, &global), Some(Retry::Resend));
+        assert_eq!(retry_kind(&program, &global), None);
+        assert_eq!(retry_kind(&anyhow::anyhow!("other"), &global), None);
+    }
+
+    #[test]
+    fn keeps_nonce_transactions_signed() {
+        let global = GlobalArgs {
+            nonce: Some(Pubkey::new_unique().to_string()),
+            ..GlobalArgs::default()
+        };
+        let expired = anyhow::Error::new(ClientError::from(ClientErrorKind::TransactionError(
+            TransactionError::BlockhashNotFound,
+        )));
+
+        assert_eq!(retry_kind(&expired, &global), None);
+    }
+
+    #[test]
+    fn decodes_base64_and_base58_transactions() {
+        let payer = Keypair::new();
+        let transaction = Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        );
+        let serialized = bincode::serialize(&transaction).unwrap();
+
+        let from_base64 = decode_transaction(&BASE64_STANDARD.encode(&serialized)).unwrap();
+        let from_base58 =
+            decode_transaction(&solana_sdk::bs58::encode(&serialized).into_string()).unwrap();
+
+        assert_eq!(from_base64, transaction);
+        assert_eq!(from_base58, transaction);
+        assert!(decode_transaction("not a transaction").is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_account_indexes() {
+        let payer = Keypair::new();
+        let mut transaction = Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        );
+        transaction.message.instructions[0].program_id_index = 99;
+        let encoded = BASE64_STANDARD.encode(bincode::serialize(&transaction).unwrap());
+
+        let error = decode_transaction(&encoded).unwrap_err();
+
+        assert_eq!(error.to_string(), "Transaction is malformed");
+    }
+}
@@ -0,0 +1,249 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::send_sol::memo_instruction;
+use crate::commands::token::{fetch_mint, format_token_amount, parse_token_amount};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+#[derive(Args)]
+pub struct TransferTokensArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    #[clap(long)]
+    pub mint: String,
+    /// Wallet receiving the tokens, its associated token account is derived
+    #[clap(long)]
+    pub recipient: String,
+    /// Amount of tokens, e.g. 12.34, scaled by the mint's decimals
+    #[clap(long)]
+    pub amount: String,
+    /// Create the recipient's associated token account if it doesn't exist yet
+    #[clap(long)]
+    pub fund_recipient: bool,
+    #[clap(long)]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TransferTokensOutput {
+    pub mint: String,
+    pub source: String,
+    pub recipient: String,
+    pub destination: String,
+    /// Amount in base units
+    pub amount: u64,
+    pub ui_amount: String,
+    pub decimals: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for TransferTokensOutput {
+    fn print_text(&self) {
+        if let Some(memo) = &self.memo {
+            println!("📝 - Memo added to transaction: {}", memo);
+        }
+        self.transaction.print_text("Tokens transferred");
+        println!("💰 - Amount: {}", self.ui_amount);
+        println!("🔑 - Recipient token account: {}", self.destination);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([
+            ExplorerLink::Address(&self.mint),
+            ExplorerLink::Address(&self.source),
+            ExplorerLink::Address(&self.recipient),
+            ExplorerLink::Address(&self.destination),
+        ]);
+        links
+    }
+}
+
+pub fn execute(args: TransferTokensArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    let sender = crate::commands::load_keypair(global)?;
+    let output = run(&client, global, &sender, args)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    args: TransferTokensArgs,
+) -> Result<TransferTokensOutput> {
+    let sender_pubkey = sender.pubkey();
+    let mint: Pubkey = args.mint.parse()?;
+    let recipient: Pubkey = args.recipient.parse()?;
+
+    let mint_info = fetch_mint(client, &mint)?;
+    let amount = parse_token_amount(&args.amount, mint_info.decimals)?;
+
+    let source =
+        get_associated_token_address_with_program_id(&sender_pubkey, &mint, &mint_info.program_id);
+    let destination =
+        get_associated_token_address_with_program_id(&recipient, &mint, &mint_info.program_id);
+
+    let mut instructions = Vec::new();
+    if args.fund_recipient {
+        instructions.push(create_associated_token_account_idempotent(
+            &sender_pubkey,
+            &recipient,
+            &mint,
+            &mint_info.program_id,
+        ));
+    }
+
+    instructions.push(spl_token_2022::instruction::transfer_checked(
+        &mint_info.program_id,
+        &source,
+        &mint,
+        &destination,
+        &sender_pubkey,
+        &[],
+        amount,
+        mint_info.decimals,
+    )?);
+
+    if let Some(memo) = &args.memo {
+        instructions.push(memo_instruction(memo)?);
+    }
+
+    let transaction =
+        process_transaction(client, global, &instructions, &sender_pubkey, &[sender])?;
+
+    Ok(TransferTokensOutput {
+        mint: mint.to_string(),
+        source: source.to_string(),
+        recipient: recipient.to_string(),
+        destination: destination.to_string(),
+        amount,
+        ui_amount: format_token_amount(amount, mint_info.decimals),
+        decimals: mint_info.decimals,
+        memo: args.memo,
+        transaction,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        account_response, assert_instructions, mint_account, MockRpc,
+    };
+    use solana_client::rpc_request::RpcRequest;
+
+    fn rpc_with_mint(mint: &Pubkey, program_id: &Pubkey) -> MockRpc {
+        let account = mint_account(program_id, &Pubkey::new_unique(), 6);
+        MockRpc::with_mocks([(RpcRequest::GetAccountInfo, account_response(mint, &account))].into())
+    }
+
+    fn args(mint: &Pubkey, recipient: &Pubkey) -> TransferTokensArgs {
+        TransferTokensArgs {
+            cluster: String::new(),
+            mint: mint.to_string(),
+            recipient: recipient.to_string(),
+            amount: "1.5".to_string(),
+            fund_recipient: false,
+            memo: None,
+        }
+    }
+
+    #[test]
+    fn transfers_between_associated_accounts() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &spl_token::ID);
+
+        let output = run(
+            &rpc.client,
+            &GlobalArgs::default(),
+            &sender,
+            args(&mint, &recipient),
+        )
+        .unwrap();
+
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[spl_token::instruction::transfer_checked(
+                &spl_token::ID,
+                &spl_associated_token_account::get_associated_token_address(
+                    &sender.pubkey(),
+                    &mint,
+                ),
+                &mint,
+                &spl_associated_token_account::get_associated_token_address(&recipient, &mint),
+                &sender.pubkey(),
+                &[],
+                1_500_000,
+                6,
+            )
+            .unwrap()],
+        );
+        assert_eq!(output.ui_amount, "1.5");
+    }
+
+    #[test]
+    fn funds_recipient_and_adds_memo() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let rpc = rpc_with_mint(&mint, &spl_token_2022::ID);
+        let args = TransferTokensArgs {
+            fund_recipient: true,
+            memo: Some("thanks".to_string()),
+            ..args(&mint, &recipient)
+        };
+
+        run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        let program_id = spl_token_2022::ID;
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[
+                create_associated_token_account_idempotent(
+                    &sender.pubkey(),
+                    &recipient,
+                    &mint,
+                    &program_id,
+                ),
+                spl_token_2022::instruction::transfer_checked(
+                    &program_id,
+                    &get_associated_token_address_with_program_id(
+                        &sender.pubkey(),
+                        &mint,
+                        &program_id,
+                    ),
+                    &mint,
+                    &get_associated_token_address_with_program_id(&recipient, &mint, &program_id),
+                    &sender.pubkey(),
+                    &[],
+                    1_500_000,
+                    6,
+                )
+                .unwrap(),
+                memo_instruction("thanks").unwrap(),
+            ],
+        );
+    }
+}
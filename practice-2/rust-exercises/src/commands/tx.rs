@@ -0,0 +1,590 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::send_sol::{memo_instruction, MEMO_PROGRAM};
+use crate::commands::token::{fetch_mint, parse_token_amount, SOL_DECIMALS};
+use crate::commands::transaction::{
+    build_message, decode_transaction, dispatch_transaction, load_nonce_authority,
+    TransactionOutcome,
+};
+use anyhow::{bail, Context, Result};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use clap::{ArgGroup, Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token::instruction::TokenInstruction;
+use spl_token_2022::instruction::TokenInstruction as Token2022Instruction;
+use std::path::{Path, PathBuf};
+
+#[derive(Args)]
+pub struct TxArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    #[clap(subcommand)]
+    pub command: TxCommand,
+}
+
+#[derive(Subcommand)]
+pub enum TxCommand {
+    /// Build a transfer from the loaded keypair, signed only by it, and save it to a file
+    Build(BuildArgs),
+    /// Add the signature of the loaded keypair to a transaction file
+    Sign {
+        #[clap(long, default_value = "transaction.json")]
+        file: PathBuf,
+    },
+    /// Decode the instructions and signers of a transaction file
+    Inspect {
+        #[clap(long, default_value = "transaction.json")]
+        file: PathBuf,
+    },
+    /// Send a transaction file once every signature is present
+    Send {
+        #[clap(long, default_value = "transaction.json")]
+        file: PathBuf,
+    },
+}
+
+#[derive(Args)]
+#[clap(group(ArgGroup::new("asset").required(true).args(["sols", "mint"])))]
+pub struct BuildArgs {
+    /// Account paying the transaction fee, defaults to the loaded keypair
+    #[clap(long)]
+    pub fee_payer: Option<String>,
+    #[clap(long)]
+    pub recipient: String,
+    /// SOL to transfer, e.g. 0.1
+    #[clap(long)]
+    pub sols: Option<String>,
+    /// Token mint to transfer instead of SOL, between associated token accounts
+    #[clap(long, requires = "amount")]
+    pub mint: Option<String>,
+    /// Amount of tokens to transfer, e.g. 12.34
+    #[clap(long)]
+    pub amount: Option<String>,
+    #[clap(long)]
+    pub memo: Option<String>,
+    #[clap(long, default_value = "transaction.json")]
+    pub outfile: PathBuf,
+}
+
+/// Transaction file shared between the parties signing it
+#[derive(Serialize, Deserialize)]
+pub struct TransactionFile {
+    /// Base64 encoded transaction, including the signatures collected so far
+    pub transaction: String,
+    #[serde(default)]
+    pub signers: Vec<SignerStatus>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SignerStatus {
+    pub pubkey: String,
+    pub signed: bool,
+}
+
+#[derive(Serialize)]
+pub struct TransactionFileOutput {
+    pub file: String,
+    pub signers: Vec<SignerStatus>,
+}
+
+impl CommandOutput for TransactionFileOutput {
+    fn print_text(&self) {
+        println!("💾 - Transaction saved to {}", self.file);
+        print_signers(&self.signers);
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        self.signers
+            .iter()
+            .map(|signer| ExplorerLink::Address(&signer.pubkey))
+            .collect()
+    }
+}
+
+#[derive(Serialize)]
+pub struct TransactionSummary {
+    pub fee_payer: String,
+    pub recent_blockhash: String,
+    pub signers: Vec<SignerStatus>,
+    pub instructions: Vec<InstructionSummary>,
+}
+
+#[derive(Serialize)]
+pub struct InstructionSummary {
+    pub program: String,
+    pub program_id: String,
+    pub accounts: Vec<AccountSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parsed: Option<String>,
+    /// Base58 encoded instruction data
+    pub data: String,
+}
+
+#[derive(Serialize)]
+pub struct AccountSummary {
+    pub pubkey: String,
+    pub signer: bool,
+    pub writable: bool,
+}
+
+impl CommandOutput for TransactionSummary {
+    fn print_text(&self) {
+        println!("💸 - Fee payer: {}", self.fee_payer);
+        println!("🔢 - Recent blockhash: {}", self.recent_blockhash);
+        print_signers(&self.signers);
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            println!(
+                "📜 - Instruction #{}: {} ({})",
+                index, instruction.program, instruction.program_id
+            );
+            if let Some(parsed) = &instruction.parsed {
+                println!("    {}", parsed);
+            }
+            for account in &instruction.accounts {
+                let mut flags = Vec::new();
+                if account.signer {
+                    flags.push("signer");
+                }
+                if account.writable {
+                    flags.push("writable");
+                }
+                println!("    {} [{}]", account.pubkey, flags.join(", "));
+            }
+        }
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = vec![ExplorerLink::Address(&self.fee_payer)];
+        links.extend(
+            self.signers
+                .iter()
+                .map(|signer| ExplorerLink::Address(&signer.pubkey)),
+        );
+        links
+    }
+}
+
+#[derive(Serialize)]
+pub struct SendTxOutput {
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for SendTxOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Transaction sent");
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        self.transaction.explorer_links()
+    }
+}
+
+fn print_signers(signers: &[SignerStatus]) {
+    for signer in signers {
+        let status = if signer.signed { "✅" } else { "⏳" };
+        println!("{} - Signer: {}", status, signer.pubkey);
+    }
+}
+
+pub fn execute(args: TxArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    match args.command {
+        TxCommand::Build(build_args) => {
+            let sender = crate::commands::load_keypair(global)?;
+            let transaction = build(&client, global, &sender, &build_args)?;
+            global.output.print_with_links(
+                &ExplorerLinks::new(global.explorer, &client.url()),
+                &save_transaction(&transaction, &build_args.outfile)?,
+            )
+        }
+        TxCommand::Sign { file } => {
+            let signer = crate::commands::load_keypair(global)?;
+            let transaction = sign(read_transaction(&file)?, &signer)?;
+            global.output.print_with_links(
+                &ExplorerLinks::new(global.explorer, &client.url()),
+                &save_transaction(&transaction, &file)?,
+            )
+        }
+        TxCommand::Inspect { file } => global.output.print_with_links(
+            &ExplorerLinks::new(global.explorer, &client.url()),
+            &inspect(&read_transaction(&file)?),
+        ),
+        TxCommand::Send { file } => {
+            let transaction = read_transaction(&file)?;
+            global.output.print_with_links(
+                &ExplorerLinks::new(global.explorer, &client.url()),
+                &send(&client, global, &transaction)?,
+            )
+        }
+    }
+}
+
+/// Builds the transfer and signs it with `sender`, leaving the other signatures empty
+pub fn build(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &dyn Signer,
+    args: &BuildArgs,
+) -> Result<Transaction> {
+    let sender_pubkey = sender.pubkey();
+    let fee_payer = match &args.fee_payer {
+        Some(fee_payer) => fee_payer.parse()?,
+        None => sender_pubkey,
+    };
+    let recipient: Pubkey = args.recipient.parse()?;
+
+    let mut instructions = match (&args.mint, &args.sols) {
+        (Some(mint), _) => {
+            let mint = mint.parse()?;
+            let mint_info = fetch_mint(client, &mint)?;
+            let amount = parse_token_amount(
+                args.amount.as_deref().unwrap_or_default(),
+                mint_info.decimals,
+            )?;
+            vec![spl_token_2022::instruction::transfer_checked(
+                &mint_info.program_id,
+                &get_associated_token_address_with_program_id(
+                    &sender_pubkey,
+                    &mint,
+                    &mint_info.program_id,
+                ),
+                &mint,
+                &get_associated_token_address_with_program_id(
+                    &recipient,
+                    &mint,
+                    &mint_info.program_id,
+                ),
+                &sender_pubkey,
+                &[],
+                amount,
+                mint_info.decimals,
+            )?]
+        }
+        (None, Some(sols)) => vec![solana_sdk::system_instruction::transfer(
+            &sender_pubkey,
+            &recipient,
+            parse_token_amount(sols, SOL_DECIMALS)?,
+        )],
+        (None, None) => bail!("Either --sols or --mint is required"),
+    };
+
+    if let Some(memo) = &args.memo {
+        instructions.push(memo_instruction(memo)?);
+    }
+
+    let nonce_authority = match load_nonce_authority(global)? {
+        Some(authority) => authority.pubkey(),
+        None => sender_pubkey,
+    };
+    let message = build_message(client, global, &instructions, &fee_payer, &nonce_authority)?;
+
+    sign(Transaction::new_unsigned(message), sender)
+}
+
+/// Adds the signature of `signer`, keeping the signatures already present
+pub fn sign(mut transaction: Transaction, signer: &dyn Signer) -> Result<Transaction> {
+    let pubkey = signer.pubkey();
+    if !transaction.message.signer_keys().contains(&&pubkey) {
+        bail!("{} is not a required signer of this transaction", pubkey);
+    }
+
+    let recent_blockhash = transaction.message.recent_blockhash;
+    transaction
+        .try_partial_sign(&[signer], recent_blockhash)
+        .context("Failed to sign transaction")?;
+    Ok(transaction)
+}
+
+pub fn send(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    transaction: &Transaction,
+) -> Result<SendTxOutput> {
+    let missing: Vec<String> = signer_statuses(transaction)
+        .into_iter()
+        .filter(|signer| !signer.signed)
+        .map(|signer| signer.pubkey)
+        .collect();
+    if !missing.is_empty() {
+        bail!(
+            "Transaction is missing signatures from {}",
+            missing.join(", ")
+        );
+    }
+
+    transaction
+        .verify()
+        .context("Transaction has invalid signatures")?;
+
+    Ok(SendTxOutput {
+        transaction: dispatch_transaction(client, global, transaction)?,
+    })
+}
+
+pub fn inspect(transaction: &Transaction) -> TransactionSummary {
+    let message = &transaction.message;
+    let instructions = message
+        .instructions
+        .iter()
+        .map(|instruction| {
+            let program_id = message.account_keys[instruction.program_id_index as usize];
+            let accounts = instruction
+                .accounts
+                .iter()
+                .map(|&index| AccountSummary {
+                    pubkey: message.account_keys[index as usize].to_string(),
+                    signer: message.is_signer(index as usize),
+                    writable: message.is_writable(index as usize),
+                })
+                .collect();
+
+            InstructionSummary {
+                program: program_name(&program_id).to_string(),
+                program_id: program_id.to_string(),
+                accounts,
+                parsed: parse_instruction(&program_id, &instruction.data),
+                data: solana_sdk::bs58::encode(&instruction.data).into_string(),
+            }
+        })
+        .collect();
+
+    TransactionSummary {
+        fee_payer: message.account_keys[0].to_string(),
+        recent_blockhash: message.recent_blockhash.to_string(),
+        signers: signer_statuses(transaction),
+        instructions,
+    }
+}
+
+fn program_name(program_id: &Pubkey) -> &'static str {
+    match program_id {
+        id if *id == solana_sdk::system_program::ID => "System Program",
+        id if *id == spl_token::ID => "Token Program",
+        id if *id == spl_token_2022::ID => "Token-2022 Program",
+        id if *id == spl_associated_token_account::ID => "Associated Token Account Program",
+        id if *id == mpl_token_metadata::ID => "Token Metadata Program",
+        id if *id == solana_sdk::compute_budget::ID => "Compute Budget Program",
+        id if id.to_string() == MEMO_PROGRAM => "Memo Program",
+        _ => "Unknown Program",
+    }
+}
+
+fn parse_instruction(program_id: &Pubkey, data: &[u8]) -> Option<String> {
+    match program_id {
+        id if *id == solana_sdk::system_program::ID => {
+            bincode::deserialize::<SystemInstruction>(data)
+                .ok()
+                .map(|instruction| format!("{:?}", instruction))
+        }
+        id if *id == spl_token::ID => TokenInstruction::unpack(data)
+            .ok()
+            .map(|instruction| format!("{:?}", instruction)),
+        id if *id == spl_token_2022::ID => Token2022Instruction::unpack(data)
+            .ok()
+            .map(|instruction| format!("{:?}", instruction)),
+        id if *id == solana_sdk::compute_budget::ID => {
+            solana_sdk::borsh1::try_from_slice_unchecked::<ComputeBudgetInstruction>(data)
+                .ok()
+                .map(|instruction| format!("{:?}", instruction))
+        }
+        id if id.to_string() == MEMO_PROGRAM => std::str::from_utf8(data)
+            .ok()
+            .map(|memo| format!("Memo: {}", memo)),
+        _ => None,
+    }
+}
+
+fn signer_statuses(transaction: &Transaction) -> Vec<SignerStatus> {
+    transaction
+        .message
+        .signer_keys()
+        .into_iter()
+        .zip(&transaction.signatures)
+        .map(|(pubkey, signature)| SignerStatus {
+            pubkey: pubkey.to_string(),
+            signed: *signature != Default::default(),
+        })
+        .collect()
+}
+
+fn read_transaction(path: &Path) -> Result<Transaction> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read transaction file {}", path.display()))?;
+    let file: TransactionFile = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid transaction file {}", path.display()))?;
+    decode_transaction(&file.transaction)
+}
+
+fn save_transaction(transaction: &Transaction, path: &Path) -> Result<TransactionFileOutput> {
+    let file = TransactionFile {
+        transaction: BASE64_STANDARD.encode(bincode::serialize(transaction)?),
+        signers: signer_statuses(transaction),
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&file)?)
+        .with_context(|| format!("Failed to write transaction file {}", path.display()))?;
+
+    Ok(TransactionFileOutput {
+        file: path.display().to_string(),
+        signers: file.signers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        account_response, assert_instructions, mint_account, MockRpc,
+    };
+    use solana_client::rpc_request::RpcRequest;
+    use solana_sdk::signature::Keypair;
+
+    fn build_args(fee_payer: &Pubkey, recipient: &Pubkey) -> BuildArgs {
+        BuildArgs {
+            fee_payer: Some(fee_payer.to_string()),
+            recipient: recipient.to_string(),
+            sols: None,
+            mint: None,
+            amount: Some("3".to_string()),
+            memo: None,
+            outfile: PathBuf::new(),
+        }
+    }
+
+    #[test]
+    fn builds_partially_signed_token_transfer() {
+        let sender = Keypair::new();
+        let receiver = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let account = mint_account(&spl_token::ID, &sender.pubkey(), 2);
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&mint, &account),
+            )]
+            .into(),
+        );
+        let args = BuildArgs {
+            mint: Some(mint.to_string()),
+            ..build_args(&receiver.pubkey(), &receiver.pubkey())
+        };
+
+        let transaction = build(&rpc.client, &GlobalArgs::default(), &sender, &args).unwrap();
+
+        let statuses: Vec<_> = signer_statuses(&transaction)
+            .into_iter()
+            .map(|signer| (signer.pubkey, signer.signed))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                (receiver.pubkey().to_string(), false),
+                (sender.pubkey().to_string(), true)
+            ]
+        );
+        assert!(send(&rpc.client, &GlobalArgs::default(), &transaction).is_err());
+
+        let transaction = sign(transaction, &receiver).unwrap();
+        send(&rpc.client, &GlobalArgs::default(), &transaction).unwrap();
+
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &receiver.pubkey(),
+            &[spl_token::instruction::transfer_checked(
+                &spl_token::ID,
+                &spl_associated_token_account::get_associated_token_address(
+                    &sender.pubkey(),
+                    &mint,
+                ),
+                &mint,
+                &spl_associated_token_account::get_associated_token_address(
+                    &receiver.pubkey(),
+                    &mint,
+                ),
+                &sender.pubkey(),
+                &[],
+                300,
+                2,
+            )
+            .unwrap()],
+        );
+    }
+
+    #[test]
+    fn rejects_unexpected_signer() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let args = BuildArgs {
+            sols: Some("0.5".to_string()),
+            ..build_args(&sender.pubkey(), &Pubkey::new_unique())
+        };
+
+        let transaction = build(&rpc.client, &GlobalArgs::default(), &sender, &args).unwrap();
+
+        assert!(sign(transaction, &Keypair::new()).is_err());
+    }
+
+    #[test]
+    fn inspects_instructions() {
+        let rpc = MockRpc::new();
+        let sender = Keypair::new();
+        let recipient = Pubkey::new_unique();
+        let args = BuildArgs {
+            sols: Some("0.5".to_string()),
+            memo: Some("hello".to_string()),
+            ..build_args(&sender.pubkey(), &recipient)
+        };
+
+        let transaction = build(&rpc.client, &GlobalArgs::default(), &sender, &args).unwrap();
+        let summary = inspect(&transaction);
+
+        assert_eq!(summary.fee_payer, sender.pubkey().to_string());
+        assert_eq!(summary.instructions.len(), 2);
+        assert_eq!(summary.instructions[0].program, "System Program");
+        assert_eq!(
+            summary.instructions[0].parsed.as_deref(),
+            Some("Transfer { lamports: 500000000 }")
+        );
+        assert_eq!(summary.instructions[1].program, "Memo Program");
+        assert_eq!(
+            summary.instructions[1].parsed.as_deref(),
+            Some("Memo: hello")
+        );
+    }
+
+    #[test]
+    fn inspects_token_2022_instructions() {
+        let payer = Keypair::new();
+        let transfer = spl_token_2022::instruction::transfer_checked(
+            &spl_token_2022::ID,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &payer.pubkey(),
+            &[],
+            1_500,
+            2,
+        )
+        .unwrap();
+        let transaction = Transaction::new_with_payer(&[transfer], Some(&payer.pubkey()));
+
+        let summary = inspect(&transaction);
+
+        assert_eq!(summary.instructions[0].program, "Token-2022 Program");
+        assert_eq!(
+            summary.instructions[0].parsed.as_deref(),
+            Some("TransferChecked { amount: 1500, decimals: 2 }")
+        );
+    }
+}
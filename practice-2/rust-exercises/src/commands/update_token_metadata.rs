@@ -0,0 +1,479 @@
+use crate::cli::GlobalArgs;
+use crate::commands::output::{CommandOutput, ExplorerLink, ExplorerLinks};
+use crate::commands::token_metadata::{
+    fetch_metadata, fetch_native_metadata_mint, parse_key_value, validate_metadata,
+};
+use crate::commands::transaction::{process_transaction, TransactionOutcome};
+use anyhow::{bail, Result};
+use clap::{ArgGroup, Args};
+use mpl_token_metadata::instructions::UpdateV1Builder;
+use mpl_token_metadata::types::Data;
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use spl_token_metadata_interface::instruction as token_metadata_instruction;
+use spl_token_metadata_interface::state::Field;
+
+#[derive(Args)]
+#[clap(group(
+    ArgGroup::new("changes")
+        .required(true)
+        .multiple(true)
+        .args(["name", "symbol", "uri", "fields", "remove_keys", "immutable"])
+))]
+pub struct UpdateTokenMetadataArgs {
+    #[clap(long, default_value = "https://api.devnet.solana.com")]
+    pub cluster: String,
+    #[clap(long)]
+    pub token_mint: String,
+    #[clap(long)]
+    pub name: Option<String>,
+    #[clap(long)]
+    pub symbol: Option<String>,
+    #[clap(long)]
+    pub uri: Option<String>,
+    /// Lock the metadata, no further updates are possible afterwards
+    #[clap(long)]
+    pub immutable: bool,
+    /// Update the token metadata extension of a Token-2022 mint instead of a Metaplex account
+    #[clap(long)]
+    pub native_metadata: bool,
+    /// Custom field as KEY=VALUE, added or overwritten, repeat for every field
+    #[clap(
+        long = "field",
+        value_name = "KEY=VALUE",
+        value_parser = parse_key_value,
+        requires = "native_metadata"
+    )]
+    pub fields: Vec<(String, String)>,
+    /// Custom field to remove, repeat for every field
+    #[clap(long = "remove-key", value_name = "KEY", requires = "native_metadata")]
+    pub remove_keys: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct UpdateTokenMetadataOutput {
+    pub mint: String,
+    pub metadata: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub is_mutable: bool,
+    #[serde(flatten)]
+    pub transaction: TransactionOutcome,
+}
+
+impl CommandOutput for UpdateTokenMetadataOutput {
+    fn print_text(&self) {
+        self.transaction.print_text("Token metadata updated");
+        println!("🔑 - Token mint address: {}", self.mint);
+        if !self.is_mutable {
+            println!("🔒 - Metadata is now immutable");
+        }
+    }
+
+    fn explorer_links(&self) -> Vec<ExplorerLink<'_>> {
+        let mut links = self.transaction.explorer_links();
+        links.extend([
+            ExplorerLink::Address(&self.mint),
+            ExplorerLink::Address(&self.metadata),
+        ]);
+        links
+    }
+}
+
+pub fn execute(args: UpdateTokenMetadataArgs, global: &GlobalArgs) -> Result<()> {
+    let client = RpcClient::new(args.cluster.clone());
+    global
+        .output
+        .status(format!("Connected to {}", args.cluster));
+
+    let sender = crate::commands::load_keypair(global)?;
+    let output = run(&client, global, &sender, args)?;
+
+    global
+        .output
+        .print_with_links(&ExplorerLinks::new(global.explorer, &client.url()), &output)
+}
+
+/// Updates the metadata of a mint, keeping the fields not passed as they are on chain
+pub fn run(
+    client: &RpcClient,
+    global: &GlobalArgs,
+    sender: &Keypair,
+    args: UpdateTokenMetadataArgs,
+) -> Result<UpdateTokenMetadataOutput> {
+    let sender_pubkey = sender.pubkey();
+    let token_mint: Pubkey = args.token_mint.parse()?;
+
+    let update = if args.native_metadata {
+        native_update(client, &sender_pubkey, &token_mint, &args)?
+    } else {
+        metaplex_update(client, &sender_pubkey, &token_mint, &args)?
+    };
+
+    let transaction = process_transaction(
+        client,
+        global,
+        &update.instructions,
+        &sender_pubkey,
+        &[sender],
+    )?;
+
+    Ok(UpdateTokenMetadataOutput {
+        mint: token_mint.to_string(),
+        metadata: update.metadata.to_string(),
+        name: update.name,
+        symbol: update.symbol,
+        uri: update.uri,
+        is_mutable: !args.immutable,
+        transaction,
+    })
+}
+
+// Instructions of an update together with the resulting metadata
+struct MetadataUpdate {
+    metadata: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    instructions: Vec<Instruction>,
+}
+
+fn metaplex_update(
+    client: &RpcClient,
+    sender: &Pubkey,
+    token_mint: &Pubkey,
+    args: &UpdateTokenMetadataArgs,
+) -> Result<MetadataUpdate> {
+    let (metadata_pda, metadata) = fetch_metadata(client, token_mint)?;
+    if !metadata.is_mutable {
+        bail!("Metadata of mint {} is immutable", token_mint);
+    }
+    if metadata.update_authority != *sender {
+        bail!(
+            "Update authority {} is not the loaded keypair",
+            metadata.update_authority
+        );
+    }
+
+    let data = Data {
+        name: args.name.clone().unwrap_or(metadata.name),
+        symbol: args.symbol.clone().unwrap_or(metadata.symbol),
+        uri: args.uri.clone().unwrap_or(metadata.uri),
+        seller_fee_basis_points: metadata.seller_fee_basis_points,
+        creators: metadata.creators,
+    };
+    validate_metadata(&data.name, &data.symbol, &data.uri)?;
+
+    let mut update = UpdateV1Builder::new();
+    update
+        .authority(*sender)
+        .mint(*token_mint)
+        .metadata(metadata_pda)
+        .payer(*sender)
+        .data(data.clone());
+    if args.immutable {
+        update.is_mutable(false);
+    }
+
+    Ok(MetadataUpdate {
+        metadata: metadata_pda,
+        name: data.name,
+        symbol: data.symbol,
+        uri: data.uri,
+        instructions: vec![update.instruction()],
+    })
+}
+
+/// Updates the token metadata extension of a Token-2022 mint, field by field
+fn native_update(
+    client: &RpcClient,
+    sender: &Pubkey,
+    token_mint: &Pubkey,
+    args: &UpdateTokenMetadataArgs,
+) -> Result<MetadataUpdate> {
+    let native_mint = fetch_native_metadata_mint(client, token_mint)?;
+    let Some(current) = &native_mint.metadata else {
+        bail!(
+            "Mint {} has no metadata, create it with create-token-metadata --native-metadata",
+            token_mint
+        );
+    };
+    match Option::<Pubkey>::from(current.update_authority) {
+        None => bail!("Metadata of mint {} is immutable", token_mint),
+        Some(authority) if authority != *sender => {
+            bail!("Update authority {} is not the loaded keypair", authority)
+        }
+        Some(_) => {}
+    }
+
+    let mut fields = Vec::new();
+    for (field, value) in [
+        (Field::Name, &args.name),
+        (Field::Symbol, &args.symbol),
+        (Field::Uri, &args.uri),
+    ] {
+        if let Some(value) = value {
+            fields.push((field, value.clone()));
+        }
+    }
+    fields.extend(
+        args.fields
+            .iter()
+            .map(|(key, value)| (Field::Key(key.clone()), value.clone())),
+    );
+
+    // Metadata after each instruction, a field growing before a removal shrinks the data
+    // again still needs its rent
+    let mut metadata = current.clone();
+    let mut states = Vec::new();
+    for (field, value) in &fields {
+        metadata.update(field.clone(), value.clone());
+        states.push(metadata.clone());
+    }
+    for key in &args.remove_keys {
+        if !metadata.remove_key(key) {
+            bail!("Metadata of mint {} has no field {}", token_mint, key);
+        }
+        states.push(metadata.clone());
+    }
+
+    let program_id = spl_token_2022::ID;
+    let mut instructions: Vec<Instruction> = native_mint
+        .rent_top_up(client, sender, token_mint, &states)?
+        .into_iter()
+        .collect();
+    instructions.extend(fields.into_iter().map(|(field, value)| {
+        token_metadata_instruction::update_field(&program_id, token_mint, sender, field, value)
+    }));
+    instructions.extend(args.remove_keys.iter().map(|key| {
+        token_metadata_instruction::remove_key(&program_id, token_mint, sender, key.clone(), false)
+    }));
+    if args.immutable {
+        instructions.push(token_metadata_instruction::update_authority(
+            &program_id,
+            token_mint,
+            sender,
+            None.try_into()?,
+        ));
+    }
+
+    Ok(MetadataUpdate {
+        metadata: *token_mint,
+        name: metadata.name,
+        symbol: metadata.symbol,
+        uri: metadata.uri,
+        instructions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_utils::{
+        account_response, assert_instructions, metadata_account, native_metadata_mint_account,
+        MockRpc,
+    };
+    use mpl_token_metadata::accounts::Metadata;
+    use solana_client::rpc_request::RpcRequest;
+    use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+    use spl_token_metadata_interface::state::TokenMetadata;
+
+    fn rpc_with_metadata(mint: &Pubkey, authority: &Pubkey) -> MockRpc {
+        let account = metadata_account(mint, authority, "Token", "TKN", "https://example.com");
+        let response = account_response(&Metadata::find_pda(mint).0, &account);
+        MockRpc::with_mocks([(RpcRequest::GetAccountInfo, response)].into())
+    }
+
+    fn args(mint: &Pubkey) -> UpdateTokenMetadataArgs {
+        UpdateTokenMetadataArgs {
+            cluster: String::new(),
+            token_mint: mint.to_string(),
+            name: None,
+            symbol: None,
+            uri: None,
+            immutable: false,
+            native_metadata: false,
+            fields: Vec::new(),
+            remove_keys: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn keeps_fields_not_updated() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_metadata(&mint, &sender.pubkey());
+        let args = UpdateTokenMetadataArgs {
+            uri: Some("https://example.com/v2.json".to_string()),
+            immutable: true,
+            ..args(&mint)
+        };
+
+        let output = run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        assert_eq!(output.name, "Token");
+        let expected = UpdateV1Builder::new()
+            .authority(sender.pubkey())
+            .mint(mint)
+            .metadata(Metadata::find_pda(&mint).0)
+            .payer(sender.pubkey())
+            .data(Data {
+                name: "Token".to_string(),
+                symbol: "TKN".to_string(),
+                uri: "https://example.com/v2.json".to_string(),
+                seller_fee_basis_points: 0,
+                creators: None,
+            })
+            .is_mutable(false)
+            .instruction();
+        assert_instructions(&rpc.sent_transaction(), &sender.pubkey(), &[expected]);
+    }
+
+    #[test]
+    fn rejects_too_long_symbol() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let rpc = rpc_with_metadata(&mint, &sender.pubkey());
+        let args = UpdateTokenMetadataArgs {
+            symbol: Some("LONGSYMBOL1".to_string()),
+            ..args(&mint)
+        };
+
+        assert!(run(&rpc.client, &GlobalArgs::default(), &sender, args).is_err());
+        assert!(rpc.sent_transactions().is_empty());
+    }
+
+    fn native_metadata(mint: &Pubkey, authority: &Pubkey) -> TokenMetadata {
+        TokenMetadata {
+            update_authority: Some(*authority).try_into().unwrap(),
+            mint: *mint,
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            uri: "https://example.com".to_string(),
+            additional_metadata: vec![("rarity".to_string(), "common".to_string())],
+        }
+    }
+
+    #[test]
+    fn updates_native_fields() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let metadata = native_metadata(&mint, &sender.pubkey());
+        let account = native_metadata_mint_account(&mint, &sender.pubkey(), Some(&metadata));
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&mint, &account),
+            )]
+            .into(),
+        );
+        let args = UpdateTokenMetadataArgs {
+            name: Some("Renamed token".to_string()),
+            native_metadata: true,
+            fields: vec![("website".to_string(), "https://example.com".to_string())],
+            remove_keys: vec!["rarity".to_string()],
+            ..args(&mint)
+        };
+
+        let output = run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        assert_eq!(output.metadata, mint.to_string());
+        assert_eq!(output.name, "Renamed token");
+        let program_id = spl_token_2022::ID;
+        // The mock RPC reports 20 lamports as the rent exempt minimum, the mint holds 1
+        assert_instructions(
+            &rpc.sent_transaction(),
+            &sender.pubkey(),
+            &[
+                solana_sdk::system_instruction::transfer(&sender.pubkey(), &mint, 19),
+                token_metadata_instruction::update_field(
+                    &program_id,
+                    &mint,
+                    &sender.pubkey(),
+                    Field::Name,
+                    "Renamed token".to_string(),
+                ),
+                token_metadata_instruction::update_field(
+                    &program_id,
+                    &mint,
+                    &sender.pubkey(),
+                    Field::Key("website".to_string()),
+                    "https://example.com".to_string(),
+                ),
+                token_metadata_instruction::remove_key(
+                    &program_id,
+                    &mint,
+                    &sender.pubkey(),
+                    "rarity".to_string(),
+                    false,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn funds_largest_intermediate_native_metadata() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let metadata = TokenMetadata {
+            additional_metadata: vec![("rarity".to_string(), "r".repeat(100))],
+            ..native_metadata(&mint, &sender.pubkey())
+        };
+        let account = native_metadata_mint_account(&mint, &sender.pubkey(), Some(&metadata));
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&mint, &account),
+            )]
+            .into(),
+        );
+        let args = UpdateTokenMetadataArgs {
+            uri: Some(format!("https://example.com/{}", "u".repeat(50))),
+            native_metadata: true,
+            remove_keys: vec!["rarity".to_string()],
+            ..args(&mint)
+        };
+
+        run(&rpc.client, &GlobalArgs::default(), &sender, args).unwrap();
+
+        // The data only shrinks once the removal runs after the longer URI is stored
+        let mut grown = metadata.clone();
+        grown.update(
+            Field::Uri,
+            format!("https://example.com/{}", "u".repeat(50)),
+        );
+        let grown_len = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data)
+            .unwrap()
+            .try_get_new_account_len_for_variable_len_extension(&grown)
+            .unwrap();
+        assert!(grown_len > account.data.len());
+        assert_eq!(rpc.rent_queries(), [grown_len]);
+    }
+
+    #[test]
+    fn rejects_removing_missing_native_field() {
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let metadata = native_metadata(&mint, &sender.pubkey());
+        let account = native_metadata_mint_account(&mint, &sender.pubkey(), Some(&metadata));
+        let rpc = MockRpc::with_mocks(
+            [(
+                RpcRequest::GetAccountInfo,
+                account_response(&mint, &account),
+            )]
+            .into(),
+        );
+        let args = UpdateTokenMetadataArgs {
+            native_metadata: true,
+            remove_keys: vec!["website".to_string()],
+            ..args(&mint)
+        };
+
+        assert!(run(&rpc.client, &GlobalArgs::default(), &sender, args).is_err());
+        assert!(rpc.sent_transactions().is_empty());
+    }
+}
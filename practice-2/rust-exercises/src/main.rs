@@ -1,6 +1,10 @@
 use crate::cli::{Cli, Commands};
+use crate::commands::freeze_account::FreezeAction;
 use crate::commands::{
-    create_token_account, create_token_metadata, create_token_mint, mint_tokens, send_sol,
+    broadcast, burn, close_token_account, create_token_account, create_token_metadata,
+    create_token_mint, distribute, favorites, freeze_account, inspect, metadata_json, mint_tokens,
+    multisig, nonce, send_sol, set_authority, show_token_metadata, transfer_tokens, tx,
+    update_token_metadata,
 };
 use anyhow::Result;
 use clap::Parser;
@@ -9,14 +13,34 @@ mod cli;
 mod commands;
 
 fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
     let cli = Cli::parse();
+    let global = &cli.global;
 
     match cli.command {
-        Commands::SendSol(args) => send_sol::execute(args)?,
-        Commands::CreateTokenMint(args) => create_token_mint::execute(args)?,
-        Commands::CreateTokenAccount(args) => create_token_account::execute(args)?,
-        Commands::MintTokens(args) => mint_tokens::execute(args)?,
-        Commands::CreateTokenMetadata(args) => create_token_metadata::execute(args)?,
+        Commands::SendSol(args) => send_sol::execute(args, global)?,
+        Commands::CreateTokenMint(args) => create_token_mint::execute(args, global)?,
+        Commands::CreateTokenAccount(args) => create_token_account::execute(args, global)?,
+        Commands::MintTokens(args) => mint_tokens::execute(args, global)?,
+        Commands::TransferTokens(args) => transfer_tokens::execute(args, global)?,
+        Commands::Distribute(args) => distribute::execute(args, global)?,
+        Commands::Burn(args) => burn::execute(args, global)?,
+        Commands::FreezeAccount(args) => {
+            freeze_account::execute(args, global, FreezeAction::Freeze)?
+        }
+        Commands::ThawAccount(args) => freeze_account::execute(args, global, FreezeAction::Thaw)?,
+        Commands::CloseTokenAccount(args) => close_token_account::execute(args, global)?,
+        Commands::SetAuthority(args) => set_authority::execute(args, global)?,
+        Commands::CreateTokenMetadata(args) => create_token_metadata::execute(args, global)?,
+        Commands::UpdateTokenMetadata(args) => update_token_metadata::execute(args, global)?,
+        Commands::ShowTokenMetadata(args) => show_token_metadata::execute(args, global)?,
+        Commands::MetadataJson(args) => metadata_json::execute(args, global)?,
+        Commands::Favorites(args) => favorites::execute(args, global)?,
+        Commands::Nonce(args) => nonce::execute(args, global)?,
+        Commands::Broadcast(args) => broadcast::execute(args, global)?,
+        Commands::Tx(args) => tx::execute(args, global)?,
+        Commands::Multisig(args) => multisig::execute(args, global)?,
+        Commands::Inspect(args) => inspect::execute(args, global)?,
     }
 
     Ok(())